@@ -26,6 +26,78 @@ struct Rule {
     length: usize,
 }
 
+/// Countries and languages of a registration group.
+struct Locale {
+    prefix: String,
+    countries: Vec<String>,
+    languages: Vec<String>,
+    multi_region: bool,
+}
+
+/// Parse the curated countries and languages of the registration groups.
+fn parse_locales(text: &str) -> Vec<Locale> {
+    let codes = |column: &str, length: usize, uppercase: bool| -> Vec<String> {
+        if column == "-" {
+            return Vec::new();
+        }
+        column
+            .split(',')
+            .map(|code| {
+                assert!(
+                    code.len() == length
+                        && code.chars().all(|c| c.is_ascii_alphabetic())
+                        && code.chars().all(|c| c.is_ascii_uppercase() == uppercase),
+                    "Invalid code {:?} in the registration group locales.",
+                    code
+                );
+                code.to_string()
+            })
+            .collect()
+    };
+
+    text.lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let columns: Vec<&str> = line.split('\t').collect();
+            assert_eq!(columns.len(), 4, "Expected four columns in {:?}.", line);
+            let locale = Locale {
+                prefix: columns[0].to_string(),
+                countries: codes(columns[1], 2, true),
+                languages: codes(columns[2], 2, false),
+                multi_region: match columns[3] {
+                    "multi" => true,
+                    "single" => false,
+                    other => panic!("Expected multi or single, found {:?}.", other),
+                },
+            };
+            // International groups such as 978-92 list no countries.
+            assert!(
+                !locale.multi_region || locale.countries.len() != 1,
+                "Expected more than one country or - for the multi-region group {:?}.",
+                locale.prefix
+            );
+            locale
+        })
+        .collect()
+}
+
+/// Check that every locale names a registration group of the RangeMessage, and only once.
+fn check_locales(locales: &[Locale], registration_groups: &[Group]) {
+    let prefixes: Vec<String> = registration_groups.iter().map(format_prefix).collect();
+    for (i, locale) in locales.iter().enumerate() {
+        assert!(
+            prefixes.contains(&locale.prefix),
+            "The registration group {:?} of the locales is not in the RangeMessage.",
+            locale.prefix
+        );
+        assert!(
+            locales[..i].iter().all(|l| l.prefix != locale.prefix),
+            "The registration group {:?} is listed more than once in the locales.",
+            locale.prefix
+        );
+    }
+}
+
 /// Parse registration group and registrant range length rules.
 fn parse_rules(group: Node) -> Vec<Rule> {
     group
@@ -97,8 +169,48 @@ fn parse_group(group: Node) -> Group {
     }
 }
 
-/// Generate code for EAN.UCC or registration group lookup.
-fn codegen_find_group(name: &str, groups: Vec<Group>, check_registration_group: bool) -> Function {
+/// Format the prefix of an EAN.UCC prefix or registration group, such as `978-89`.
+fn format_prefix(group: &Group) -> String {
+    let mut prefix: String = group.prefix.iter().map(|d| d.to_string()).collect();
+    if !group.registration_group_element.is_empty() {
+        prefix.push('-');
+        prefix.extend(
+            group
+                .registration_group_element
+                .iter()
+                .map(|d| d.to_string()),
+        );
+    }
+    prefix
+}
+
+/// Generate a static table of EAN.UCC prefixes or registration groups, along with their rules.
+fn codegen_group_table(name: &str, groups: &[Group]) -> String {
+    let mut table = format!(
+        "#[allow(clippy::unreadable_literal)]\nstatic {}: [RangeGroup<'static>; {}] = [\n",
+        name,
+        groups.len()
+    );
+    for group in groups {
+        table.push_str(&format!(
+            "    RangeGroup {{\n        prefix: {:?},\n        agency: {:?},\n        rules: &[\n",
+            format_prefix(group),
+            group.agency
+        ));
+        for rule in &group.rules {
+            table.push_str(&format!(
+                "            Rule {{ start: {}, stop: {}, length: {} }},\n",
+                rule.min, rule.max, rule.length
+            ));
+        }
+        table.push_str("        ],\n    },\n");
+    }
+    table.push_str("];");
+    table
+}
+
+/// Generate code finding the index of an EAN.UCC prefix or registration group in its table.
+fn codegen_find_group(name: &str, groups: &[Group], check_registration_group: bool) -> Function {
     let mut fn_get_group = Function::new(name);
     fn_get_group.arg("prefix", "u16");
 
@@ -106,8 +218,7 @@ fn codegen_find_group(name: &str, groups: Vec<Group>, check_registration_group:
         fn_get_group.arg("registration_group_element", "u32");
     }
 
-    fn_get_group.arg("segment", "u32");
-    fn_get_group.ret("Result<Group<'static>, IsbnError>");
+    fn_get_group.ret("Option<usize>");
 
     let mut match_prefix = if check_registration_group {
         Block::new("match (prefix, registration_group_element)")
@@ -115,10 +226,10 @@ fn codegen_find_group(name: &str, groups: Vec<Group>, check_registration_group:
         Block::new("match prefix")
     };
 
-    for group in groups {
+    for (index, group) in groups.iter().enumerate() {
         match_prefix.line(if check_registration_group {
             format!(
-                "({:#X}, {:#X}) =>",
+                "({:#X}, {:#X}) => Some({}),",
                 ((group.prefix[0] as u16) << 8)
                     | ((group.prefix[1] as u16) << 4)
                     | (group.prefix[2] as u16),
@@ -128,46 +239,44 @@ fn codegen_find_group(name: &str, groups: Vec<Group>, check_registration_group:
                         digits = (digits << 4) | digit as u32;
                     }
                     digits
-                }
+                },
+                index
             )
         } else {
             format!(
-                "{:#X} =>",
+                "{:#X} => Some({}),",
                 ((group.prefix[0] as u16) << 8)
                     | ((group.prefix[1] as u16) << 4)
-                    | (group.prefix[2] as u16)
+                    | (group.prefix[2] as u16),
+                index
             )
         });
-
-        let mut let_length_eq_match_segment = Block::new("let length = match segment");
-        for rule in &group.rules {
-            let_length_eq_match_segment.line(match rule.length {
-                0 => format!(
-                    "{} ..= {} => Err(IsbnError::UndefinedRange),",
-                    rule.min, rule.max
-                ),
-                _ => format!("{} ..= {} => Ok({}),", rule.min, rule.max, rule.length),
-            });
-        }
-        let_length_eq_match_segment.line("_ => Err(IsbnError::InvalidGroup)");
-        let_length_eq_match_segment.after(";");
-
-        let mut ok_group = Block::new("Ok(Group");
-        ok_group.line(format!("name: \"{}\",", group.agency));
-        ok_group.line("segment_length: length?");
-        ok_group.after(")");
-
-        let mut segment_match_block = Block::new("");
-        segment_match_block.push_block(let_length_eq_match_segment);
-        segment_match_block.push_block(ok_group);
-
-        match_prefix.push_block(segment_match_block);
     }
-    match_prefix.line("_ => Err(IsbnError::InvalidGroup)");
+    match_prefix.line("_ => None");
     fn_get_group.push_block(match_prefix);
     fn_get_group
 }
 
+/// Generate code for registration group locale lookup.
+fn codegen_find_locale(locales: &[Locale]) -> Function {
+    let mut fn_find_locale = Function::new("find_group_locale");
+    fn_find_locale.arg("prefix", "&str");
+    fn_find_locale.ret("Option<GroupLocale<'static>>");
+
+    let mut match_prefix = Block::new("match prefix");
+    for locale in locales {
+        let mut some_locale = Block::new(format!("{:?} => Some(GroupLocale", locale.prefix));
+        some_locale.line(format!("countries: &{:?},", locale.countries));
+        some_locale.line(format!("languages: &{:?},", locale.languages));
+        some_locale.line(format!("multi_region: {},", locale.multi_region));
+        some_locale.after("),");
+        match_prefix.push_block(some_locale);
+    }
+    match_prefix.line("_ => None");
+    fn_find_locale.push_block(match_prefix);
+    fn_find_locale
+}
+
 fn main() {
     let mut f = File::open("./isbn-ranges/RangeMessage.xml").unwrap();
     let mut text = String::new();
@@ -177,12 +286,12 @@ fn main() {
         ..Default::default()
     };
     let range_message = Document::parse_with_options(&text, options).unwrap();
-    let ean_ucc_groups = range_message
+    let ean_ucc_groups: Vec<Group> = range_message
         .descendants()
         .filter(|d| d.tag_name().name() == "EAN.UCC")
         .map(parse_group)
         .collect();
-    let registration_groups = range_message
+    let registration_groups: Vec<Group> = range_message
         .descendants()
         .filter(|d| d.tag_name().name() == "Group")
         .map(parse_group)
@@ -191,20 +300,41 @@ fn main() {
     let mut scope = Scope::new();
     let impl_isbn = scope.new_impl("Isbn");
     impl_isbn.push_fn(codegen_find_group(
-        "get_ean_ucc_group",
-        ean_ucc_groups,
+        "find_ean_ucc_group",
+        &ean_ucc_groups,
         false,
     ));
     impl_isbn.push_fn(codegen_find_group(
-        "get_registration_group",
-        registration_groups,
+        "find_registration_group",
+        &registration_groups,
         true,
     ));
 
+    let mut locales = String::new();
+    File::open("./data/group-locales.tsv")
+        .unwrap()
+        .read_to_string(&mut locales)
+        .unwrap();
+    let locales = parse_locales(&locales);
+    check_locales(&locales, &registration_groups);
+    impl_isbn.push_fn(codegen_find_locale(&locales));
+
     let out_dir = env::var("OUT_DIR").unwrap();
     let dest_path = Path::new(&out_dir).join("generated.rs");
 
     let mut f = File::create(&dest_path).unwrap();
+    writeln!(
+        f,
+        "{}",
+        codegen_group_table("EAN_UCC_GROUPS", &ean_ucc_groups)
+    )
+    .unwrap();
+    writeln!(
+        f,
+        "{}",
+        codegen_group_table("REGISTRATION_GROUPS", &registration_groups)
+    )
+    .unwrap();
     f.write_all(ALLOW_LINTS.trim_start().as_bytes()).unwrap();
     writeln!(f).unwrap();
     f.write_all(scope.to_string().as_bytes()).unwrap();
@@ -0,0 +1,197 @@
+use arrayvec::ArrayString;
+
+use crate::{CompiledRanges, Isbn, IsbnError, RangeSource, check_hyphens};
+
+/// How a stored, hyphenated ISBN compares with the current ISBN ranges.
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+pub enum AuditStatus {
+    /// The ISBN is hyphenated as given by the ranges, or is stored without hyphens.
+    Unchanged,
+    /// The ISBN is in the same registration group, but the registrant has a different length.
+    /// Contains the current hyphenation.
+    Rehyphenated(ArrayString<17>),
+    /// The registration group of the ISBN has a different length. Contains the current
+    /// hyphenation.
+    MovedGroup(ArrayString<17>),
+    /// The ISBN can no longer be hyphenated, such as when its range is not defined for use at
+    /// this time.
+    Undefined(IsbnError),
+    /// The string is not an ISBN.
+    Invalid(IsbnError),
+}
+
+/// An iterator comparing stored, hyphenated ISBNs with the ISBN ranges of a [`RangeSource`],
+/// returned by [`Isbn::audit`] and [`RangeSource::audit`].
+///
+/// Yields each string along with its [`AuditStatus`].
+///
+/// # Examples
+///
+/// ```
+/// use isbn::{AuditStatus, Isbn};
+///
+/// let stored = ["978-1-4920-6766-5", "978-14920-6766-5", "9781492067665", "978-1-4920-6766-6"];
+/// let statuses: Vec<AuditStatus> = Isbn::audit(stored).map(|(_, status)| status).collect();
+///
+/// assert_eq!(statuses[0], AuditStatus::Unchanged);
+/// assert_eq!(
+///     statuses[1],
+///     AuditStatus::MovedGroup("978-1-4920-6766-5".try_into().unwrap())
+/// );
+/// assert_eq!(statuses[2], AuditStatus::Unchanged);
+/// assert!(matches!(statuses[3], AuditStatus::Invalid(_)));
+/// ```
+#[derive(Debug)]
+pub struct Audit<'a, I, S: ?Sized = CompiledRanges> {
+    hyphenated: I,
+    source: &'a S,
+}
+
+impl<'a, I, S: RangeSource + ?Sized> Audit<'a, I, S> {
+    pub(crate) fn new(hyphenated: I, source: &'a S) -> Self {
+        Audit { hyphenated, source }
+    }
+
+    fn audit(&self, s: &str) -> AuditStatus {
+        // Surrounding whitespace is ignored when parsing, so it must not count as a separator.
+        let s = s.trim();
+        let isbn: Isbn = match s.parse() {
+            Ok(isbn) => isbn,
+            Err(e) => return AuditStatus::Invalid(e),
+        };
+        let hyphenated = match self.source.hyphenate((&isbn).into()) {
+            Ok(hyphenated) => hyphenated,
+            Err(e) => return AuditStatus::Undefined(e),
+        };
+        if check_hyphens(s, hyphenated).is_ok() {
+            return AuditStatus::Unchanged;
+        }
+
+        // The registration group follows the GS1 prefix of ISBN13s.
+        let group = match isbn {
+            Isbn::_10(_) => 0,
+            Isbn::_13(_) => 1,
+        };
+        let stored_group = s.split(['-', ' ']).filter(|e| !e.is_empty()).nth(group);
+        if stored_group == hyphenated.split('-').nth(group) {
+            AuditStatus::Rehyphenated(hyphenated)
+        } else {
+            AuditStatus::MovedGroup(hyphenated)
+        }
+    }
+}
+
+impl<I: Clone, S: ?Sized> Clone for Audit<'_, I, S> {
+    fn clone(&self) -> Self {
+        Audit {
+            hyphenated: self.hyphenated.clone(),
+            source: self.source,
+        }
+    }
+}
+
+impl<I, S> Iterator for Audit<'_, I, S>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+    S: RangeSource + ?Sized,
+{
+    type Item = (I::Item, AuditStatus);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let s = self.hyphenated.next()?;
+        let status = self.audit(s.as_ref());
+        Some((s, status))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.hyphenated.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(s: &str) -> AuditStatus {
+        Isbn::audit([s]).next().unwrap().1
+    }
+
+    fn hyphenated(s: &str) -> ArrayString<17> {
+        ArrayString::from(s).unwrap()
+    }
+
+    #[test]
+    fn test_unchanged() {
+        for s in [
+            "978-1-4920-6766-5",
+            "978 1 4920 6766 5",
+            "9781492067665",
+            "89-6626-126-4",
+            "89 6626 126 4",
+            "8966261264",
+        ] {
+            assert_eq!(status(s), AuditStatus::Unchanged, "{}", s);
+        }
+    }
+
+    #[test]
+    fn test_surrounding_whitespace() {
+        for s in [
+            " 978-1-4920-6766-5",
+            "978-1-4920-6766-5 ",
+            "\t89 6626 126 4  ",
+        ] {
+            assert_eq!(status(s), AuditStatus::Unchanged, "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn test_rehyphenated() {
+        let isbn_13 = AuditStatus::Rehyphenated(hyphenated("978-1-4920-6766-5"));
+        assert_eq!(status("978-1-49206-766-5"), isbn_13);
+        assert_eq!(status("978 1 49206 766 5"), isbn_13);
+
+        // The registration group is the first element of an ISBN10.
+        let isbn_10 = AuditStatus::Rehyphenated(hyphenated("89-6626-126-4"));
+        assert_eq!(status("89-66261-26-4"), isbn_10);
+        assert_eq!(status("89 66261 26 4"), isbn_10);
+    }
+
+    #[test]
+    fn test_moved_group() {
+        let isbn_13 = AuditStatus::MovedGroup(hyphenated("978-1-4920-6766-5"));
+        assert_eq!(status("978-14-920-6766-5"), isbn_13);
+        assert_eq!(status("978 14 920 6766 5"), isbn_13);
+
+        let isbn_10 = AuditStatus::MovedGroup(hyphenated("89-6626-126-4"));
+        assert_eq!(status("896-626-126-4"), isbn_10);
+        assert_eq!(status("8 966 26126 4"), isbn_10);
+    }
+
+    #[test]
+    fn test_undefined() {
+        let undefined = AuditStatus::Undefined(IsbnError::UndefinedRange);
+        assert_eq!(status("979-0-000-00000-1"), undefined);
+        assert_eq!(status("979 0 000 00000 1"), undefined);
+        assert_eq!(status("80-99999-99-1"), undefined);
+        assert_eq!(status("80 99999 99 1"), undefined);
+        assert_eq!(
+            status("99999-0000-3"),
+            AuditStatus::Undefined(IsbnError::InvalidGroup)
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        let checksum = AuditStatus::Invalid(IsbnError::InvalidChecksum);
+        assert_eq!(status("978-1-4920-6766-6"), checksum);
+        assert_eq!(status("978 1 4920 6766 6"), checksum);
+        assert_eq!(status("89-6626-126-5"), checksum);
+        assert_eq!(status("89 6626 126 5"), checksum);
+        assert_eq!(
+            status("978-1-4920-676"),
+            AuditStatus::Invalid(IsbnError::InvalidLength)
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use core::fmt;
+use core::str::FromStr;
+
+use arrayvec::ArrayString;
+
+use crate::{CompiledRanges, Isbn13, IsbnError, IsbnObject, IsbnRef};
+
+/// The block of ISBNs available to a single registrant, identified by a hyphenated registrant
+/// prefix such as `978-1-4920`.
+///
+/// # Examples
+///
+/// ```
+/// use isbn::{Isbn13, IsbnBlock};
+///
+/// let block: IsbnBlock = "978-1-4920".parse().unwrap();
+/// assert_eq!(block.size(), 10_000);
+///
+/// let isbn_13: Isbn13 = "978-1-4920-6766-5".parse().unwrap();
+/// assert!(block.contains(&isbn_13));
+/// assert_eq!(block.publication(&isbn_13), Some(6766));
+///
+/// let next: Vec<Isbn13> = block.iter_from(6767).take(2).collect();
+/// assert_eq!(next[0].hyphenate().unwrap().as_str(), "978-1-4920-6767-2");
+///
+/// assert!("978-1-492".parse::<IsbnBlock>().is_err());
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub struct IsbnBlock {
+    body: [u8; 12],
+    hyphen_at: [usize; 2],
+}
+
+impl IsbnBlock {
+    /// Parses a registrant prefix, checking its registration group and registrant against the
+    /// ranges found by `locate`.
+    pub(crate) fn parse_with<F>(s: &str, locate: F) -> Result<IsbnBlock, IsbnError>
+    where
+        F: FnOnce(&Isbn13) -> Result<[usize; 2], IsbnError>,
+    {
+        let mut elements = s.split('-');
+        let (prefix, group, registrant) = match (
+            elements.next(),
+            elements.next(),
+            elements.next(),
+            elements.next(),
+        ) {
+            (Some(group), Some(registrant), None, None) => ("978", group, registrant),
+            (Some(prefix), Some(group), Some(registrant), None) => (prefix, group, registrant),
+            _ => return Err(IsbnError::InvalidLength),
+        };
+        if prefix.len() != 3
+            || group.is_empty()
+            || registrant.is_empty()
+            || prefix.len() + group.len() + registrant.len() > 11
+        {
+            return Err(IsbnError::InvalidLength);
+        }
+
+        let mut body = [0; 12];
+        for (digit, c) in body.iter_mut().zip(
+            prefix
+                .chars()
+                .chain(group.chars())
+                .chain(registrant.chars()),
+        ) {
+            *digit = c.to_digit(10).ok_or(IsbnError::InvalidDigit)? as u8;
+        }
+
+        let hyphen_at = locate(&Isbn13::from_body(body)?)?;
+        if hyphen_at[0] != group.len() {
+            Err(IsbnError::InvalidGroup)
+        } else if hyphen_at[1] != group.len() + registrant.len() {
+            Err(IsbnError::InvalidRegistrant)
+        } else {
+            Ok(IsbnBlock { body, hyphen_at })
+        }
+    }
+
+    /// The number of digits before the publication element.
+    fn prefix_length(&self) -> usize {
+        3 + self.hyphen_at[1]
+    }
+
+    /// The number of digits in the publication element.
+    pub fn publication_length(&self) -> usize {
+        12 - self.prefix_length()
+    }
+
+    /// The number of ISBNs in the block.
+    pub fn size(&self) -> u32 {
+        10_u32.pow(self.publication_length() as u32)
+    }
+
+    /// Whether the ISBN belongs to the block.
+    pub fn contains<'a, I: Into<IsbnRef<'a>>>(&self, isbn: I) -> bool {
+        self.publication(isbn).is_some()
+    }
+
+    /// The publication number of an ISBN in the block.
+    pub fn publication<'a, I: Into<IsbnRef<'a>>>(&self, isbn: I) -> Option<u32> {
+        let isbn = match isbn.into() {
+            IsbnRef::_10(isbn) => Isbn13::from(*isbn),
+            IsbnRef::_13(isbn) => *isbn,
+        };
+        let length = self.prefix_length();
+        if isbn.digits[..length] != self.body[..length] {
+            return None;
+        }
+        Some(
+            isbn.digits[length..12]
+                .iter()
+                .fold(0, |n, &digit| n * 10 + u32::from(digit)),
+        )
+    }
+
+    /// The ISBN with the given publication number, if it is in the block.
+    pub fn get(&self, publication: u32) -> Option<Isbn13> {
+        if publication >= self.size() {
+            return None;
+        }
+        let mut body = self.body;
+        let mut n = publication;
+        for digit in body[self.prefix_length()..].iter_mut().rev() {
+            *digit = (n % 10) as u8;
+            n /= 10;
+        }
+        Isbn13::from_body(body).ok()
+    }
+
+    /// Iterate over every ISBN in the block, in order.
+    pub fn iter(&self) -> Publications {
+        self.iter_from(0)
+    }
+
+    /// Iterate over the ISBNs in the block, starting at the given publication number.
+    pub fn iter_from(&self, publication: u32) -> Publications {
+        Publications {
+            block: *self,
+            next: publication.min(self.size()),
+            end: self.size(),
+        }
+    }
+}
+
+impl fmt::Display for IsbnBlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut s = ArrayString::<14>::new();
+        for (i, &digit) in self.body[..self.prefix_length()].iter().enumerate() {
+            if i == 3 || i == 3 + self.hyphen_at[0] {
+                s.push('-');
+            }
+            s.push(char::from(b'0' + digit));
+        }
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for IsbnBlock {
+    type Err = IsbnError;
+    fn from_str(s: &str) -> Result<IsbnBlock, IsbnError> {
+        IsbnBlock::parse_with(s, |isbn| Ok(isbn.trait_locate(&CompiledRanges)?.0))
+    }
+}
+
+impl IntoIterator for &IsbnBlock {
+    type Item = Isbn13;
+    type IntoIter = Publications;
+
+    fn into_iter(self) -> Publications {
+        self.iter()
+    }
+}
+
+/// An iterator over the ISBNs in an [`IsbnBlock`].
+#[derive(Debug, Clone)]
+pub struct Publications {
+    block: IsbnBlock,
+    next: u32,
+    end: u32,
+}
+
+impl Iterator for Publications {
+    type Item = Isbn13;
+
+    fn next(&mut self) -> Option<Isbn13> {
+        if self.next == self.end {
+            return None;
+        }
+        self.next += 1;
+        self.block.get(self.next - 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.end - self.next) as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Publications {
+    fn next_back(&mut self) -> Option<Isbn13> {
+        if self.next == self.end {
+            return None;
+        }
+        self.end -= 1;
+        self.block.get(self.end)
+    }
+}
+
+impl ExactSizeIterator for Publications {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Isbn10;
+    use core::fmt::Write;
+
+    #[test]
+    fn test_block_from_str() {
+        let block: IsbnBlock = "978-0-306".parse().unwrap();
+        assert_eq!(block.size(), 100_000);
+        assert_eq!(block.publication_length(), 5);
+        assert_eq!(block, "0-306".parse().unwrap());
+
+        let block: IsbnBlock = "978-99921-58".parse().unwrap();
+        assert_eq!(block.size(), 100);
+
+        assert_eq!(
+            "978-03-06".parse::<IsbnBlock>(),
+            Err(IsbnError::InvalidGroup)
+        );
+        assert_eq!(
+            "978-0-3065".parse::<IsbnBlock>(),
+            Err(IsbnError::InvalidRegistrant)
+        );
+        assert_eq!("978".parse::<IsbnBlock>(), Err(IsbnError::InvalidLength));
+        assert_eq!(
+            "978-0-306-4061".parse::<IsbnBlock>(),
+            Err(IsbnError::InvalidLength)
+        );
+        assert_eq!(
+            "978-0-30X".parse::<IsbnBlock>(),
+            Err(IsbnError::InvalidDigit)
+        );
+        assert_eq!(
+            "979-0-306".parse::<IsbnBlock>(),
+            Err(IsbnError::UndefinedRange)
+        );
+    }
+
+    #[test]
+    fn test_block_publications() {
+        let block: IsbnBlock = "978-99921-58".parse().unwrap();
+        let isbn_10: Isbn10 = "99921-58-10-7".parse().unwrap();
+        assert!(block.contains(&isbn_10));
+        assert_eq!(block.publication(&isbn_10), Some(10));
+
+        assert_eq!(block.iter().len(), 100);
+        for (i, isbn) in block.iter().enumerate() {
+            assert!(block.contains(&isbn));
+            assert_eq!(block.publication(&isbn), Some(i as u32));
+            assert!(isbn.hyphenate().unwrap().starts_with("978-99921-58-"));
+        }
+        assert_eq!(block.iter().next_back(), block.get(99));
+        assert_eq!(block.iter_from(101).next(), None);
+        assert_eq!(block.get(100), None);
+
+        let other: Isbn13 = "978-0-306-40615-7".parse().unwrap();
+        assert!(!block.contains(&other));
+    }
+
+    #[test]
+    fn test_block_display() {
+        for s in ["978-0-306", "978-99921-58", "979-10-90636"] {
+            let mut displayed = ArrayString::<14>::new();
+            write!(displayed, "{}", s.parse::<IsbnBlock>().unwrap()).unwrap();
+            assert_eq!(displayed.as_str(), s);
+        }
+    }
+}
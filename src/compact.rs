@@ -0,0 +1,167 @@
+use core::fmt;
+use core::num::NonZeroU64;
+use core::str::FromStr;
+
+use arrayvec::ArrayString;
+
+use crate::{Isbn, Isbn10, Isbn13, IsbnError, IsbnParts};
+
+/// An ISBN13 packed into eight bytes, for storing large numbers of ISBNs.
+///
+/// `Option<CompactIsbn>` is also eight bytes. ISBN10s are stored as their equivalent ISBN13.
+///
+/// # Examples
+///
+/// ```
+/// use isbn::{CompactIsbn, Isbn13};
+///
+/// let isbn_13: Isbn13 = "978-1-4920-6766-5".parse().unwrap();
+/// let compact = CompactIsbn::from(isbn_13);
+///
+/// assert_eq!(size_of::<Option<CompactIsbn>>(), 8);
+/// assert_eq!(compact.to_u64(), 9781492067665);
+/// assert_eq!(compact.hyphenate().unwrap().as_str(), "978-1-4920-6766-5");
+/// assert_eq!(compact.registration_group(), Ok("English language"));
+/// assert_eq!(Isbn13::from(compact), isbn_13);
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash)]
+pub struct CompactIsbn(
+    /// The ISBN13 as a number, plus one so that the all-zero ISBN13 is representable.
+    NonZeroU64,
+);
+
+impl CompactIsbn {
+    /// Packs an ISBN13.
+    pub const fn new(isbn13: Isbn13) -> CompactIsbn {
+        match NonZeroU64::new(isbn13.to_u64() + 1) {
+            Some(n) => CompactIsbn(n),
+            None => unreachable!(),
+        }
+    }
+
+    /// The ISBN13 as a 13-digit number.
+    pub const fn to_u64(self) -> u64 {
+        self.0.get() - 1
+    }
+
+    /// Unpacks the ISBN13.
+    pub const fn to_isbn13(self) -> Isbn13 {
+        let mut n = self.to_u64();
+        let mut digits = [0; 13];
+        let mut i = 13;
+        while i > 0 {
+            i -= 1;
+            digits[i] = (n % 10) as u8;
+            n /= 10;
+        }
+        Isbn13 { digits }
+    }
+
+    /// Hyphenate the ISBN into its parts, as with [`Isbn13::hyphenate`].
+    ///
+    /// # Errors
+    /// If the ISBN is not valid, as determined by the current ISBN rules, an error will be
+    /// returned.
+    pub fn hyphenate(self) -> Result<ArrayString<17>, IsbnError> {
+        self.to_isbn13().hyphenate()
+    }
+
+    /// Retrieve the name of the registration group, as with [`Isbn13::registration_group`].
+    ///
+    /// # Errors
+    /// If the ISBN is not valid, as determined by the current ISBN rules, an error will be
+    /// returned.
+    pub fn registration_group(self) -> Result<&'static str, IsbnError> {
+        Ok(self.parts()?.agency())
+    }
+
+    /// Split the ISBN into its parts, as with [`Isbn13::parts`].
+    ///
+    /// # Errors
+    /// If the ISBN is not valid, as determined by the current ISBN rules, an error will be
+    /// returned.
+    pub fn parts(self) -> Result<IsbnParts<'static>, IsbnError> {
+        self.to_isbn13().parts()
+    }
+}
+
+impl From<Isbn13> for CompactIsbn {
+    fn from(isbn13: Isbn13) -> CompactIsbn {
+        CompactIsbn::new(isbn13)
+    }
+}
+
+impl From<Isbn10> for CompactIsbn {
+    fn from(isbn10: Isbn10) -> CompactIsbn {
+        CompactIsbn::new(isbn10.into())
+    }
+}
+
+impl From<Isbn> for CompactIsbn {
+    fn from(isbn: Isbn) -> CompactIsbn {
+        CompactIsbn::new(isbn.canonical())
+    }
+}
+
+impl From<CompactIsbn> for Isbn13 {
+    fn from(compact: CompactIsbn) -> Isbn13 {
+        compact.to_isbn13()
+    }
+}
+
+impl TryFrom<u64> for CompactIsbn {
+    type Error = IsbnError;
+    fn try_from(n: u64) -> Result<CompactIsbn, IsbnError> {
+        Isbn13::try_from(n).map(CompactIsbn::new)
+    }
+}
+
+impl fmt::Display for CompactIsbn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.to_isbn13().fmt(f)
+    }
+}
+
+impl FromStr for CompactIsbn {
+    type Err = IsbnError;
+    fn from_str(s: &str) -> Result<CompactIsbn, IsbnError> {
+        s.parse::<Isbn>().map(CompactIsbn::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compact_round_trip() {
+        for s in [
+            "0-306-40615-2",
+            "0-9752298-0-X",
+            "978-3-16-148410-0",
+            "979-10-90636-07-1",
+        ] {
+            let isbn: Isbn = s.parse().unwrap();
+            let compact: CompactIsbn = s.parse().unwrap();
+            assert_eq!(compact.to_u64(), isbn.to_u64());
+            assert_eq!(compact.to_isbn13().to_u64(), isbn.to_u64());
+            assert_eq!(CompactIsbn::try_from(compact.to_u64()), Ok(compact));
+        }
+
+        let zero = Isbn13::new([0; 13]).unwrap();
+        assert_eq!(CompactIsbn::new(zero).to_isbn13(), zero);
+        assert_eq!(size_of::<Option<CompactIsbn>>(), size_of::<u64>());
+    }
+
+    #[test]
+    fn test_compact_lookups() {
+        let isbn: Isbn13 = "978-3-16-148410-0".parse().unwrap();
+        let compact = CompactIsbn::from(isbn);
+        assert_eq!(compact.hyphenate(), isbn.hyphenate());
+        assert_eq!(compact.registration_group(), isbn.registration_group());
+        assert_eq!(compact.parts(), isbn.parts());
+
+        let undefined: CompactIsbn = "979-0-000-00000-1".parse().unwrap();
+        assert_eq!(undefined.hyphenate(), Err(IsbnError::UndefinedRange));
+    }
+}
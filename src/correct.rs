@@ -0,0 +1,168 @@
+use arrayvec::ArrayVec;
+
+use crate::{Isbn, Isbn10, Isbn13, IsbnError, Parser};
+
+/// The most corrections an ISBN13 can have: one substitution for each of its 13 digits, and one
+/// transposition for each of its 12 adjacent pairs.
+const MAX_CORRECTIONS: usize = 25;
+
+/// The kind of typo a [`Correction`] undoes.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub enum Typo {
+    /// The digits at the given index and the one after it were swapped.
+    Transposition(usize),
+    /// The check digit was mistyped.
+    CheckDigit,
+    /// The digit at the given index was mistyped.
+    Substitution(usize),
+}
+
+/// A valid ISBN which differs from a mistyped one by a single typo, returned by
+/// [`Isbn::corrections`].
+#[derive(Debug, PartialEq, Clone, Eq, Hash)]
+pub struct Correction {
+    isbn: Isbn,
+    typo: Typo,
+}
+
+impl Correction {
+    /// The corrected ISBN.
+    pub fn isbn(&self) -> &Isbn {
+        &self.isbn
+    }
+
+    /// The typo which was undone.
+    pub fn typo(&self) -> Typo {
+        self.typo
+    }
+}
+
+/// An iterator over the corrections for a mistyped ISBN, from most to least plausible.
+#[derive(Debug, Clone)]
+pub struct Corrections {
+    corrections: arrayvec::IntoIter<Correction, MAX_CORRECTIONS>,
+}
+
+impl Iterator for Corrections {
+    type Item = Correction;
+
+    fn next(&mut self) -> Option<Correction> {
+        self.corrections.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.corrections.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Corrections {}
+
+fn to_isbn(digits: &[u8]) -> Option<Isbn> {
+    match digits.len() {
+        10 => Isbn10::new(digits.try_into().ok()?).ok().map(Isbn::_10),
+        13 => Isbn13::new(digits.try_into().ok()?).ok().map(Isbn::_13),
+        _ => None,
+    }
+}
+
+impl Corrections {
+    pub(crate) fn new(s: &str) -> Result<Corrections, IsbnError> {
+        let digits = Parser::new(s)?.digits;
+        if !matches!(digits.len(), 10 | 13) {
+            return Err(IsbnError::InvalidLength);
+        }
+
+        let mut corrections = ArrayVec::<Correction, MAX_CORRECTIONS>::new();
+        let mut push = |candidate: &[u8], typo| {
+            if let Some(isbn) = to_isbn(candidate).filter(|isbn| isbn.hyphenate().is_ok()) {
+                corrections.push(Correction { isbn, typo });
+            }
+        };
+
+        for i in 0..digits.len() - 1 {
+            if digits[i] != digits[i + 1] {
+                let mut candidate = digits.clone();
+                candidate.swap(i, i + 1);
+                push(&candidate, Typo::Transposition(i));
+            }
+        }
+
+        let last = digits.len() - 1;
+        let max_check_digit = if last == 9 { 10 } else { 9 };
+        for digit in (0..=max_check_digit).filter(|&digit| digit != digits[last]) {
+            let mut candidate = digits.clone();
+            candidate[last] = digit;
+            push(&candidate, Typo::CheckDigit);
+        }
+
+        for i in 0..last {
+            for digit in (0..=9).filter(|&digit| digit != digits[i]) {
+                let mut candidate = digits.clone();
+                candidate[i] = digit;
+                push(&candidate, Typo::Substitution(i));
+            }
+        }
+
+        Ok(Corrections {
+            corrections: corrections.into_iter(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_corrections_are_valid() {
+        for s in [
+            "978-3-16-148410-1",
+            "978-3-61-148410-0",
+            "0-306-40615-3",
+            "0-306-46015-2",
+        ] {
+            for correction in Isbn::corrections(s).unwrap() {
+                assert!(correction.isbn().hyphenate().is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn test_corrections_ranked() {
+        let expected: Isbn = "978-3-16-148410-0".parse().unwrap();
+
+        let mut corrections = Isbn::corrections("978-3-61-148410-0").unwrap();
+        let first = corrections.next().unwrap();
+        assert_eq!(first.isbn(), &expected);
+        assert_eq!(first.typo(), Typo::Transposition(4));
+
+        let corrections = Isbn::corrections("978-3-16-148410-1").unwrap();
+        let check_digit = corrections
+            .take_while(|c| matches!(c.typo(), Typo::Transposition(_) | Typo::CheckDigit))
+            .find(|c| c.typo() == Typo::CheckDigit)
+            .unwrap();
+        assert_eq!(check_digit.isbn(), &expected);
+    }
+
+    #[test]
+    fn test_corrections_isbn10_check_digit_x() {
+        let expected: Isbn = "0-8044-2957-X".parse().unwrap();
+        assert!(
+            Isbn::corrections("0-8044-2957-1")
+                .unwrap()
+                .any(|c| c.isbn() == &expected && c.typo() == Typo::CheckDigit)
+        );
+    }
+
+    #[test]
+    fn test_corrections_invalid_input() {
+        assert_eq!(
+            Isbn::corrections("978-3-16").unwrap_err(),
+            IsbnError::InvalidLength
+        );
+        assert_eq!(
+            Isbn::corrections("978-3-16-14841O-0").unwrap_err(),
+            IsbnError::InvalidDigit
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use core::fmt;
+
+use arrayvec::ArrayString;
+
+use crate::{IsbnError, IsbnRef};
+
+/// A style in which an ISBN can be displayed, with [`Isbn::display_with`](crate::Isbn::display_with).
+///
+/// Styles which hyphenate fall back to the compact form if the ISBN is in an undefined range.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash, Default)]
+pub enum DisplayStyle {
+    /// Digits only, such as `9781492067665`. This is how ISBNs are displayed with `{}`.
+    #[default]
+    Compact,
+    /// Hyphenated, such as `978-1-4920-6766-5`. This is how ISBNs are displayed with `{:#}`.
+    Hyphenated,
+    /// Separated by spaces, such as `978 1 4920 6766 5`.
+    Spaced,
+    /// Hyphenated with an `ISBN ` prefix, such as `ISBN 978-1-4920-6766-5`.
+    Prefixed,
+    /// Hyphenated with an `ISBN-13: ` or `ISBN-10: ` label, such as `ISBN-13: 978-1-4920-6766-5`.
+    Labelled,
+}
+
+/// Displays an ISBN in a [`DisplayStyle`], returned by
+/// [`Isbn::display_with`](crate::Isbn::display_with).
+///
+/// # Examples
+///
+/// ```
+/// use isbn::{DisplayStyle, Isbn10};
+///
+/// let isbn_10: Isbn10 = "8966261264".parse().unwrap();
+/// assert_eq!(isbn_10.display_with(DisplayStyle::Spaced).to_string(), "89 6626 126 4");
+/// assert_eq!(
+///     isbn_10.display_with(DisplayStyle::Labelled).to_string(),
+///     "ISBN-10: 89-6626-126-4"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct DisplayWith<'a> {
+    isbn: IsbnRef<'a>,
+    style: DisplayStyle,
+}
+
+impl<'a> DisplayWith<'a> {
+    pub(crate) fn new(isbn: IsbnRef<'a>, style: DisplayStyle) -> Self {
+        DisplayWith { isbn, style }
+    }
+
+    fn hyphenate(&self) -> Result<ArrayString<17>, IsbnError> {
+        match self.isbn {
+            IsbnRef::_10(isbn) => isbn.hyphenate(),
+            IsbnRef::_13(isbn) => isbn.hyphenate(),
+        }
+    }
+
+    fn fmt_compact(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.isbn {
+            IsbnRef::_10(isbn) => write!(f, "{}", isbn),
+            IsbnRef::_13(isbn) => write!(f, "{}", isbn),
+        }
+    }
+
+    fn fmt_hyphenated(&self, f: &mut fmt::Formatter, separator: char) -> fmt::Result {
+        match self.hyphenate() {
+            Ok(hyphenated) => hyphenated
+                .split('-')
+                .enumerate()
+                .try_for_each(|(i, element)| {
+                    if i > 0 {
+                        write!(f, "{}", separator)?;
+                    }
+                    write!(f, "{}", element)
+                }),
+            Err(_) => self.fmt_compact(f),
+        }
+    }
+}
+
+impl fmt::Display for DisplayWith<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.style {
+            DisplayStyle::Compact => self.fmt_compact(f),
+            DisplayStyle::Hyphenated => self.fmt_hyphenated(f, '-'),
+            DisplayStyle::Spaced => self.fmt_hyphenated(f, ' '),
+            DisplayStyle::Prefixed => {
+                write!(f, "ISBN ")?;
+                self.fmt_hyphenated(f, '-')
+            }
+            DisplayStyle::Labelled => {
+                match self.isbn {
+                    IsbnRef::_10(_) => write!(f, "ISBN-10: ")?,
+                    IsbnRef::_13(_) => write!(f, "ISBN-13: ")?,
+                }
+                self.fmt_hyphenated(f, '-')
+            }
+        }
+    }
+}
@@ -22,68 +22,338 @@
 #![deny(clippy::missing_errors_doc)]
 #![deny(clippy::if_not_else)]
 
+mod audit;
+mod block;
+mod compact;
+mod correct;
+mod display;
+mod locale;
+mod parse;
 #[cfg(feature = "runtime-ranges")]
 pub mod range;
-#[cfg(feature = "runtime-ranges")]
-pub use range::IsbnRange;
+mod rules;
+mod scan;
 #[cfg(feature = "serialize")]
-use serde::{Deserialize, Serialize};
+pub mod serialize;
+mod source;
+
+pub use audit::{Audit, AuditStatus};
+pub use block::{IsbnBlock, Publications};
+pub use compact::CompactIsbn;
+pub use correct::{Correction, Corrections, Typo};
+pub use display::{DisplayStyle, DisplayWith};
+pub use locale::GroupLocale;
+pub use parse::ParseOptions;
+#[cfg(feature = "runtime-ranges")]
+pub use range::{IsbnRange, IsbnRangeStore};
+pub use rules::{RangeGroup, RangeLookup, Rule};
+pub use scan::FindIter;
+pub use source::{CompiledRanges, RangeSource};
 
 use core::char;
+use core::cmp::Ordering;
 use core::fmt;
 use core::num::ParseIntError;
 use core::str::FromStr;
 
 use arrayvec::{ArrayString, ArrayVec, CapacityError};
 
+use rules::{check_group_length, check_registrant_length};
+
 pub type IsbnResult<T> = Result<T, IsbnError>;
 
 include!(concat!(env!("OUT_DIR"), "/generated.rs"));
 
+impl Isbn {
+    /// Compare stored, hyphenated ISBNs with the ISBN ranges, such as after the ranges are
+    /// updated. See [`Audit`] for an example.
+    pub fn audit<I>(hyphenated: I) -> Audit<'static, I::IntoIter>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        Audit::new(hyphenated.into_iter(), &CompiledRanges)
+    }
+
+    /// The EAN.UCC prefixes of the ISBN ranges, in the order of the RangeMessage.
+    ///
+    /// ```
+    /// use isbn::Isbn;
+    ///
+    /// let prefixes: Vec<&str> = Isbn::ean_ucc_groups().map(|g| g.prefix()).collect();
+    /// assert_eq!(prefixes, ["978", "979"]);
+    /// ```
+    pub fn ean_ucc_groups()
+    -> impl ExactSizeIterator<Item = RangeGroup<'static>> + DoubleEndedIterator {
+        EAN_UCC_GROUPS.iter().copied()
+    }
+
+    /// The registration groups of the ISBN ranges, in the order of the RangeMessage.
+    ///
+    /// ```
+    /// use isbn::Isbn;
+    ///
+    /// let korea = Isbn::registration_groups()
+    ///     .find(|g| g.prefix() == "978-89")
+    ///     .unwrap();
+    /// assert_eq!(korea.agency(), "Korea, Republic");
+    /// assert_eq!(korea.rules()[0].length(), 2);
+    /// ```
+    pub fn registration_groups()
+    -> impl ExactSizeIterator<Item = RangeGroup<'static>> + DoubleEndedIterator {
+        REGISTRATION_GROUPS.iter().copied()
+    }
+}
+
 struct Group<'a> {
     name: &'a str,
     segment_length: usize,
 }
 
 trait IsbnObject {
-    fn ean_ucc_group(&self) -> Result<Group<'_>, IsbnError> {
-        Isbn::get_ean_ucc_group(self.prefix_element(), self.segment(0))
-    }
-
     fn hyphenate_with(&self, hyphen_at: [usize; 2]) -> ArrayString<17>;
 
-    fn trait_hyphenate(&self) -> Result<ArrayString<17>, IsbnError> {
-        let registration_group_segment_length = self.ean_ucc_group()?.segment_length;
-        let registrant_segment_length = Isbn::get_registration_group(
-            self.prefix_element(),
-            self.group_prefix(registration_group_segment_length),
-            self.segment(registration_group_segment_length),
-        )?
-        .segment_length;
+    fn parts_with<'a>(&self, hyphen_at: [usize; 2], agency: &'a str) -> IsbnParts<'a>;
+
+    /// Finds where the registration group and registrant elements end, along with the name of
+    /// the registration group.
+    fn trait_locate<'s, S: RangeSource + ?Sized>(
+        &self,
+        source: &'s S,
+    ) -> Result<([usize; 2], &'s str), IsbnError> {
+        Ok(self.trait_locate_detailed(source)?)
+    }
+
+    fn trait_locate_detailed<'s, S: RangeSource + ?Sized>(
+        &self,
+        source: &'s S,
+    ) -> Result<([usize; 2], &'s str), DetailedError> {
+        let registration_group_segment_length = source
+            .find_ean_ucc_group(self.ean_ucc_prefix())
+            .ok_or(IsbnError::InvalidGroup)
+            .and_then(|group| group.group(self.segment(0)))
+            .and_then(|group| check_group_length(group.segment_length))
+            .map_err(|error| self.lookup_error(RangeElement::RegistrationGroup, 0, error))?;
+        let registration_group = source
+            .find_registration_group(
+                self.ean_ucc_prefix(),
+                self.group_digits(registration_group_segment_length),
+            )
+            .ok_or(IsbnError::InvalidGroup)
+            .and_then(|group| group.group(self.segment(registration_group_segment_length)))
+            .and_then(|group| {
+                check_registrant_length(registration_group_segment_length, group.segment_length)?;
+                Ok(group)
+            })
+            .map_err(|error| {
+                self.lookup_error(
+                    RangeElement::Registrant,
+                    registration_group_segment_length,
+                    error,
+                )
+            })?;
 
         let hyphen_at = [
             registration_group_segment_length,
-            registration_group_segment_length + registrant_segment_length,
+            registration_group_segment_length + registration_group.segment_length,
         ];
+        Ok((hyphen_at, registration_group.name))
+    }
+
+    fn trait_hyphenate<S: RangeSource + ?Sized>(
+        &self,
+        source: &S,
+    ) -> Result<ArrayString<17>, IsbnError> {
+        let (hyphen_at, _) = self.trait_locate(source)?;
         Ok(self.hyphenate_with(hyphen_at))
     }
 
-    fn trait_registration_group(&self) -> Result<&str, IsbnError> {
-        let registration_group_segment_length = self.ean_ucc_group()?.segment_length;
+    fn trait_registration_group<'s, S: RangeSource + ?Sized>(
+        &self,
+        source: &'s S,
+    ) -> Result<&'s str, IsbnError> {
+        let (_, name) = self.trait_locate(source)?;
+        Ok(name)
+    }
+
+    /// Looks up the groups and rules for the ISBN in the source.
+    fn trait_lookup_range<'s, S: RangeSource + ?Sized>(
+        &self,
+        source: &'s S,
+    ) -> Result<RangeLookup<'s>, IsbnError> {
+        let ean_ucc_group = source
+            .find_ean_ucc_group(self.ean_ucc_prefix())
+            .ok_or(IsbnError::InvalidGroup)?;
+        let ean_ucc_rule = *ean_ucc_group
+            .rule(self.segment(0))
+            .ok_or(IsbnError::InvalidGroup)?;
+        let mut lookup = RangeLookup {
+            ean_ucc_group,
+            ean_ucc_rule,
+            registration_group: None,
+            registrant_rule: None,
+        };
+        if !ean_ucc_rule.is_reserved() {
+            let length = check_group_length(ean_ucc_rule.length())?;
+            lookup.registration_group =
+                source.find_registration_group(self.ean_ucc_prefix(), self.group_digits(length));
+            lookup.registrant_rule = lookup
+                .registration_group
+                .and_then(|group| group.rule(self.segment(length)).copied());
+        }
+        Ok(lookup)
+    }
 
-        Ok(Isbn::get_registration_group(
-            self.prefix_element(),
-            self.group_prefix(registration_group_segment_length),
-            self.segment(registration_group_segment_length),
-        )?
-        .name)
+    fn trait_parts<'s, S: RangeSource + ?Sized>(
+        &self,
+        source: &'s S,
+    ) -> Result<IsbnParts<'s>, IsbnError> {
+        Ok(self.trait_parts_detailed(source)?)
     }
 
-    fn prefix_element(&self) -> u16;
+    fn trait_parts_detailed<'s, S: RangeSource + ?Sized>(
+        &self,
+        source: &'s S,
+    ) -> Result<IsbnParts<'s>, DetailedError> {
+        let (hyphen_at, agency) = self.trait_locate_detailed(source)?;
+        Ok(self.parts_with(hyphen_at, agency))
+    }
+
+    /// Describes a failed lookup in the rules for the GS1 prefix and the registration group
+    /// with the given length.
+    fn lookup_error(
+        &self,
+        element: RangeElement,
+        group_length: usize,
+        error: IsbnError,
+    ) -> DetailedError {
+        let mut prefix = ArrayString::new();
+        for digit in self.ean_ucc_prefix() {
+            prefix.push(convert_isbn_body(digit));
+        }
+        if group_length > 0 {
+            prefix.push('-');
+            for &digit in self.group_digits(group_length) {
+                prefix.push(convert_isbn_body(digit));
+            }
+        }
+        DetailedError::Lookup {
+            element,
+            prefix,
+            error,
+        }
+    }
+
+    /// The digits of the EAN.UCC prefix, which is always 978 for ISBN10s.
+    fn ean_ucc_prefix(&self) -> [u8; 3];
+
+    /// The nine digits between the EAN.UCC prefix and the check digit.
+    fn body(&self) -> &[u8];
 
-    fn segment(&self, base: usize) -> u32;
+    /// The seven digits following the first `base` digits of the body, padded with zeros.
+    fn segment(&self, base: usize) -> u32 {
+        (base..base + 7).fold(0, |segment, i| {
+            segment * 10 + u32::from(*self.body().get(i).unwrap_or(&0))
+        })
+    }
+
+    /// The first `length` digits of the body.
+    fn group_digits(&self, length: usize) -> &[u8] {
+        &self.body()[..length]
+    }
+}
+
+/// The elements of an ISBN, split according to the ISBN ranges.
+///
+/// # Examples
+///
+/// ```
+/// use isbn::Isbn13;
+///
+/// let isbn_13: Isbn13 = "9781492067665".parse().unwrap();
+/// let parts = isbn_13.parts().unwrap();
+///
+/// assert_eq!(parts.gs1_prefix(), Some("978"));
+/// assert_eq!(parts.registration_group(), "1");
+/// assert_eq!(parts.registrant(), "4920");
+/// assert_eq!(parts.publication(), "6766");
+/// assert_eq!(parts.check_digit(), '5');
+/// assert_eq!(parts.agency(), "English language");
+/// assert_eq!(parts.to_string(), "978-1-4920-6766-5");
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub struct IsbnParts<'a> {
+    digits: ArrayString<13>,
+    prefix_length: usize,
+    hyphen_at: [usize; 2],
+    agency: &'a str,
+}
+
+impl<'a> IsbnParts<'a> {
+    /// The GS1 prefix, or `None` for an ISBN-10.
+    pub fn gs1_prefix(&self) -> Option<&str> {
+        match self.prefix_length {
+            0 => None,
+            length => Some(&self.digits[..length]),
+        }
+    }
+
+    /// The registration group element.
+    pub fn registration_group(&self) -> &str {
+        &self.digits[self.prefix_length..self.prefix_length + self.hyphen_at[0]]
+    }
+
+    /// The registrant element.
+    pub fn registrant(&self) -> &str {
+        &self.digits[self.prefix_length + self.hyphen_at[0]..self.prefix_length + self.hyphen_at[1]]
+    }
+
+    /// The publication element.
+    pub fn publication(&self) -> &str {
+        &self.digits[self.prefix_length + self.hyphen_at[1]..self.digits.len() - 1]
+    }
+
+    /// The check digit, which is `X` for an ISBN-10 check digit of ten.
+    pub fn check_digit(&self) -> char {
+        self.digits.as_bytes()[self.digits.len() - 1].into()
+    }
+
+    /// The name of the agency of the registration group, such as `"English language"`.
+    pub fn agency(&self) -> &'a str {
+        self.agency
+    }
+
+    /// The countries and languages of the registration group, if known.
+    pub fn locale(&self) -> Option<GroupLocale<'static>> {
+        let mut prefix = ArrayString::<9>::new();
+        prefix.push_str(self.gs1_prefix().unwrap_or("978"));
+        prefix.push('-');
+        prefix.push_str(self.registration_group());
+        Isbn::find_group_locale(&prefix)
+    }
+
+    /// Joins the parts with hyphens.
+    pub fn hyphenate(&self) -> ArrayString<17> {
+        let mut hyphenated = ArrayString::new();
+        if let Some(prefix) = self.gs1_prefix() {
+            hyphenated.push_str(prefix);
+            hyphenated.push('-');
+        }
+        hyphenated.push_str(self.registration_group());
+        hyphenated.push('-');
+        hyphenated.push_str(self.registrant());
+        hyphenated.push('-');
+        hyphenated.push_str(self.publication());
+        hyphenated.push('-');
+        hyphenated.push(self.check_digit());
+        hyphenated
+    }
+}
 
-    fn group_prefix(&self, length: usize) -> u32;
+impl fmt::Display for IsbnParts<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.hyphenate())
+    }
 }
 
 /// An International Standard Book Number, either ISBN10 or ISBN13.
@@ -100,7 +370,6 @@ trait IsbnObject {
 /// assert_eq!("978-1-4920-6766-5".parse(), Ok(isbn_13));
 /// ```
 #[derive(Debug, PartialEq, Clone, Eq, Hash)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub enum Isbn {
     _10(Isbn10),
     _13(Isbn13),
@@ -134,6 +403,50 @@ impl Isbn {
         }
     }
 
+    /// Display the ISBN in the given style.
+    ///
+    /// ```
+    /// use isbn::{DisplayStyle, Isbn};
+    ///
+    /// let isbn: Isbn = "9781492067665".parse().unwrap();
+    /// assert_eq!(format!("{}", isbn), "9781492067665");
+    /// assert_eq!(format!("{:#}", isbn), "978-1-4920-6766-5");
+    /// assert_eq!(
+    ///     isbn.display_with(DisplayStyle::Prefixed).to_string(),
+    ///     "ISBN 978-1-4920-6766-5"
+    /// );
+    /// assert_eq!(
+    ///     isbn.display_with(DisplayStyle::Labelled).to_string(),
+    ///     "ISBN-13: 978-1-4920-6766-5"
+    /// );
+    /// ```
+    pub fn display_with(&self, style: DisplayStyle) -> DisplayWith<'_> {
+        DisplayWith::new(self.into(), style)
+    }
+
+    /// Look up the groups and rules of the ISBN ranges matching the ISBN, such as to explain
+    /// why it can not be hyphenated.
+    ///
+    /// ```
+    /// use isbn::Isbn;
+    ///
+    /// let isbn: Isbn = "978-1-4920-6766-5".parse().unwrap();
+    /// let lookup = isbn.lookup_range().unwrap();
+    ///
+    /// assert_eq!(lookup.registration_group().unwrap().prefix(), "978-1");
+    /// assert_eq!(lookup.registrant_rule().unwrap().length(), 4);
+    /// assert_eq!(lookup.error(), None);
+    /// ```
+    /// # Errors
+    /// If the EAN.UCC prefix of the ISBN or its rule is not found, or the rule gives the
+    /// registration group more than five digits, an error will be returned.
+    pub fn lookup_range(&self) -> Result<RangeLookup<'static>, IsbnError> {
+        match self {
+            Isbn::_10(c) => c.trait_lookup_range(&CompiledRanges),
+            Isbn::_13(c) => c.trait_lookup_range(&CompiledRanges),
+        }
+    }
+
     /// Retrieve the name of the registration group.
     ///
     /// ```
@@ -155,6 +468,205 @@ impl Isbn {
             Isbn::_13(c) => c.registration_group(),
         }
     }
+
+    /// Parse an ISBN10 or ISBN13, describing where any problem was found.
+    ///
+    /// ```
+    /// use isbn::{DetailedError, Isbn};
+    ///
+    /// assert!(Isbn::parse_detailed("89-6626-126-4").is_ok());
+    /// assert_eq!(
+    ///     Isbn::parse_detailed("89-6626-126-5"),
+    ///     Err(DetailedError::InvalidChecksum { expected: '4', found: '5' })
+    /// );
+    /// ```
+    /// # Errors
+    /// If the string is not an ISBN, an error will be returned.
+    pub fn parse_detailed(s: &str) -> Result<Isbn, DetailedError> {
+        Parser::new_detailed(s)?.read_isbn()
+    }
+
+    /// Split an ISBN into its parts, describing which lookup failed if any.
+    ///
+    /// ```
+    /// use isbn::{DetailedError, Isbn, IsbnError, RangeElement};
+    ///
+    /// let isbn: Isbn = "979-0-000-00000-1".parse().unwrap();
+    /// assert_eq!(
+    ///     isbn.parts_detailed(),
+    ///     Err(DetailedError::Lookup {
+    ///         element: RangeElement::RegistrationGroup,
+    ///         prefix: "979".try_into().unwrap(),
+    ///         error: IsbnError::UndefinedRange,
+    ///     })
+    /// );
+    /// ```
+    /// # Errors
+    /// If the ISBN is not valid, as determined by the current ISBN rules, an error will be
+    /// returned.
+    pub fn parts_detailed(&self) -> Result<IsbnParts<'static>, DetailedError> {
+        match self {
+            Isbn::_10(c) => c.trait_parts_detailed(&CompiledRanges),
+            Isbn::_13(c) => c.trait_parts_detailed(&CompiledRanges),
+        }
+    }
+
+    /// Parse an ISBN10 or ISBN13 according to the given options.
+    ///
+    /// ```
+    /// use isbn::{Isbn, ParseOptions};
+    ///
+    /// let isbn = Isbn::parse_with("ISBN 978‐3‐16‐148410‐0", &ParseOptions::lenient()).unwrap();
+    /// assert_eq!(isbn, "978-3-16-148410-0".parse().unwrap());
+    /// ```
+    /// # Errors
+    /// If the string is not an ISBN under the given options, or the checksum is not correct, an
+    /// error will be returned.
+    pub fn parse_with(s: &str, options: &ParseOptions) -> Result<Isbn, IsbnError> {
+        Ok(Parser::with_options(s, options)?.read_isbn()?)
+    }
+
+    /// Parse an ISBN10 or ISBN13, requiring any hyphens to separate the elements given by the
+    /// current ISBN rules. Spaces are treated as hyphens, and strings without any are accepted.
+    ///
+    /// ```
+    /// use isbn::{Isbn, IsbnError};
+    ///
+    /// assert!(Isbn::parse_hyphenated("978-3-16-148410-0").is_ok());
+    /// assert!(Isbn::parse_hyphenated("9783161484100").is_ok());
+    /// assert_eq!(
+    ///     Isbn::parse_hyphenated("97-83161-48410-0"),
+    ///     Err(IsbnError::InvalidHyphenation("978-3-16-148410-0".try_into().unwrap()))
+    /// );
+    /// ```
+    /// # Errors
+    /// If the string is not an ISBN, its range is not defined, or its hyphens are misplaced, an
+    /// error will be returned.
+    pub fn parse_hyphenated(s: &str) -> Result<Isbn, IsbnError> {
+        let isbn: Isbn = s.parse()?;
+        check_hyphens(s, isbn.hyphenate()?)?;
+        Ok(isbn)
+    }
+
+    /// Suggest valid ISBNs which a mistyped ISBN10 or ISBN13 may have been meant to be.
+    ///
+    /// Candidates differ from `s` by swapping two adjacent digits or by changing a single
+    /// digit, have a valid check digit, and are in a range defined by the current ISBN rules.
+    /// They are ordered from most to least plausible: transpositions first, then a mistyped
+    /// check digit, then any other mistyped digit.
+    ///
+    /// ```
+    /// use isbn::{Isbn, Typo};
+    ///
+    /// let correction = Isbn::corrections("978-3-61-148410-0").unwrap().next().unwrap();
+    /// assert_eq!(correction.isbn(), &"978-3-16-148410-0".parse().unwrap());
+    /// assert_eq!(correction.typo(), Typo::Transposition(4));
+    /// ```
+    /// # Errors
+    /// If the string does not consist of 10 or 13 digits, an error will be returned.
+    pub fn corrections(s: &str) -> Result<Corrections, IsbnError> {
+        Corrections::new(s)
+    }
+
+    /// Find every ISBN in a piece of text, along with its byte range in `text`.
+    ///
+    /// ISBNs may be written with hyphens, spaces or Unicode dashes between the digits, and be
+    /// labelled, as in `ISBN-13: 978-1-4920-6766-5`. Only ISBNs with a valid check digit, which are
+    /// not directly followed by another digit or letter, are returned.
+    ///
+    /// ```
+    /// use isbn::Isbn;
+    ///
+    /// let text = "ISBN-13: 978\u{2010}1\u{2010}4920\u{2010}6766\u{2010}5, or ISBN 89-6626-126-4.";
+    /// let found: Vec<_> = Isbn::find_iter(text).collect();
+    ///
+    /// assert_eq!(found.len(), 2);
+    /// assert_eq!(found[0].1, "9781492067665".parse().unwrap());
+    /// assert_eq!(&text[found[1].0.clone()], "89-6626-126-4");
+    /// ```
+    pub fn find_iter(text: &str) -> FindIter<'_> {
+        FindIter::new(text)
+    }
+
+    /// Split an ISBN into its parts.
+    ///
+    /// ```
+    /// use isbn::{Isbn, Isbn10, Isbn13};
+    ///
+    /// let isbn_10 = Isbn::_10(Isbn10::new([8, 9, 6, 6, 2, 6, 1, 2, 6, 4]).unwrap());
+    /// let isbn_13 = Isbn::_13(Isbn13::new([9, 7, 8, 1, 4, 9, 2, 0, 6, 7, 6, 6, 5]).unwrap());
+    ///
+    /// assert_eq!(isbn_10.parts().unwrap().registrant(), "6626");
+    /// assert_eq!(isbn_13.parts().unwrap().registrant(), "4920");
+    /// ```
+    ///
+    /// # Errors
+    /// If the ISBN is not valid, as determined by the current ISBN rules, an error will be
+    /// returned.
+    pub fn parts(&self) -> Result<IsbnParts<'static>, IsbnError> {
+        match self {
+            Isbn::_10(c) => c.parts(),
+            Isbn::_13(c) => c.parts(),
+        }
+    }
+
+    /// The ISBN13 identifying the same book, converting from ISBN10 if necessary.
+    ///
+    /// An ISBN10 and its equivalent ISBN13 are different `Isbn`s, but have the same canonical
+    /// form, which makes it suitable as a key when deduplicating books.
+    ///
+    /// ```
+    /// use isbn::Isbn;
+    ///
+    /// let isbn_10: Isbn = "89-6626-126-4".parse().unwrap();
+    /// let isbn_13: Isbn = "978-89-6626-126-0".parse().unwrap();
+    ///
+    /// assert_ne!(isbn_10, isbn_13);
+    /// assert_eq!(isbn_10.canonical(), isbn_13.canonical());
+    /// ```
+    pub fn canonical(&self) -> Isbn13 {
+        match self {
+            Isbn::_10(c) => Isbn13::from(*c),
+            Isbn::_13(c) => *c,
+        }
+    }
+
+    /// Whether two ISBNs identify the same book, even if one is an ISBN10 and the other an
+    /// ISBN13.
+    ///
+    /// ```
+    /// use isbn::Isbn;
+    ///
+    /// let isbn_10: Isbn = "89-6626-126-4".parse().unwrap();
+    /// let isbn_13: Isbn = "978-89-6626-126-0".parse().unwrap();
+    ///
+    /// assert!(isbn_10.same_book(&isbn_13));
+    /// ```
+    pub fn same_book(&self, other: &Isbn) -> bool {
+        self.canonical() == other.canonical()
+    }
+
+    /// The ISBN, or the equivalent ISBN13 of an ISBN10, as a 13-digit number.
+    ///
+    /// This loses the ISBN10 form: converting the number back to an `Isbn` always gives an
+    /// ISBN13. Only [`Isbn10::to_u64`] and [`Isbn13::to_u64`] round-trip with `TryFrom<u64>`.
+    ///
+    /// ```
+    /// use isbn::{Isbn, Isbn10, Isbn13};
+    ///
+    /// let isbn_10 = Isbn::_10(Isbn10::new([8, 9, 6, 6, 2, 6, 1, 2, 6, 4]).unwrap());
+    /// let isbn_13 = Isbn::_13(Isbn13::new([9, 7, 8, 1, 4, 9, 2, 0, 6, 7, 6, 6, 5]).unwrap());
+    ///
+    /// assert_eq!(isbn_10.to_u64(), 9788966261260);
+    /// assert_eq!(isbn_13.to_u64(), 9781492067665);
+    /// assert_eq!(Isbn::try_from(9781492067665), Ok(isbn_13));
+    ///
+    /// assert_ne!(Isbn::try_from(isbn_10.to_u64()), Ok(isbn_10.clone()));
+    /// assert_eq!(Isbn::try_from(isbn_10.to_u64()), Ok(Isbn::_13(isbn_10.canonical())));
+    /// ```
+    pub fn to_u64(&self) -> u64 {
+        self.canonical().to_u64()
+    }
 }
 
 /// An International Standard Book Number, either ISBN10 or ISBN13.
@@ -206,6 +718,47 @@ impl fmt::Display for Isbn {
     }
 }
 
+/// ISBNs are ordered by their ISBN13 value, with an ISBN10 placed right before the equivalent
+/// ISBN13.
+///
+/// ```
+/// use isbn::Isbn;
+///
+/// let mut isbns: Vec<Isbn> = ["978-89-6626-126-0", "978-1-4920-6766-5", "89-6626-126-4"]
+///     .iter()
+///     .map(|s| s.parse().unwrap())
+///     .collect();
+/// isbns.sort();
+///
+/// assert_eq!(isbns[0], "978-1-4920-6766-5".parse().unwrap());
+/// assert_eq!(isbns[1], "89-6626-126-4".parse().unwrap());
+/// assert_eq!(isbns[2], "978-89-6626-126-0".parse().unwrap());
+/// ```
+impl Ord for Isbn {
+    fn cmp(&self, other: &Isbn) -> Ordering {
+        self.to_u64()
+            .cmp(&other.to_u64())
+            .then_with(|| match (self, other) {
+                (Isbn::_10(_), Isbn::_13(_)) => Ordering::Less,
+                (Isbn::_13(_), Isbn::_10(_)) => Ordering::Greater,
+                _ => Ordering::Equal,
+            })
+    }
+}
+
+impl PartialOrd for Isbn {
+    fn partial_cmp(&self, other: &Isbn) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl TryFrom<u64> for Isbn {
+    type Error = IsbnError;
+    fn try_from(n: u64) -> Result<Isbn, IsbnError> {
+        Isbn13::try_from(n).map(Isbn::_13)
+    }
+}
+
 impl From<Isbn10> for Isbn {
     fn from(isbn10: Isbn10) -> Isbn {
         Isbn::_10(isbn10)
@@ -221,7 +774,26 @@ impl From<Isbn13> for Isbn {
 impl FromStr for Isbn {
     type Err = IsbnError;
     fn from_str(s: &str) -> Result<Isbn, IsbnError> {
-        Parser::new(s)?.read_isbn()
+        Ok(Parser::new(s)?.read_isbn()?)
+    }
+}
+
+/// Checks that the hyphens or spaces in `s` match those in `expected`.
+fn check_hyphens(s: &str, expected: ArrayString<17>) -> Result<(), IsbnError> {
+    let mut separators = s.chars().filter(|c| matches!(c, '-' | ' '));
+    if separators.next().is_none() {
+        return Ok(());
+    }
+
+    let matches = s.len() == expected.len()
+        && s.bytes().zip(expected.bytes()).all(|(a, b)| match a {
+            b' ' => b == b'-',
+            _ => a.eq_ignore_ascii_case(&b),
+        });
+    if matches {
+        Ok(())
+    } else {
+        Err(IsbnError::InvalidHyphenation(expected))
     }
 }
 
@@ -240,8 +812,7 @@ const fn convert_isbn10_check(d: u8) -> char {
 }
 
 /// 10-digit ISBN format.
-#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash)]
 pub struct Isbn10 {
     digits: [u8; 10],
 }
@@ -264,22 +835,27 @@ impl IsbnObject for Isbn10 {
         hyphenated
     }
 
-    fn prefix_element(&self) -> u16 {
-        0x978
+    fn parts_with<'a>(&self, hyphen_at: [usize; 2], agency: &'a str) -> IsbnParts<'a> {
+        let mut digits = ArrayString::new();
+        for &digit in &self.digits[0..9] {
+            digits.push(convert_isbn_body(digit));
+        }
+        digits.push(convert_isbn10_check(self.digits[9]));
+
+        IsbnParts {
+            digits,
+            prefix_length: 0,
+            hyphen_at,
+            agency,
+        }
     }
 
-    fn segment(&self, base: usize) -> u32 {
-        (0..7).fold(0, |s, i| {
-            s + u32::from(*self.digits.get(base + i).unwrap_or(&0)) * 10_u32.pow(6 - i as u32)
-        })
+    fn ean_ucc_prefix(&self) -> [u8; 3] {
+        [9, 7, 8]
     }
 
-    fn group_prefix(&self, length: usize) -> u32 {
-        let mut digits = 0;
-        for &digit in &self.digits[..length] {
-            digits = (digits << 4) | digit as u32;
-        }
-        digits
+    fn body(&self) -> &[u8] {
+        &self.digits[..9]
     }
 }
 
@@ -310,32 +886,76 @@ impl Isbn10 {
 
     /// Convert ISBN-13 to ISBN-10, if applicable.
     ///
-    /// ```
-    /// use isbn::{Isbn10, Isbn13};
+    /// This shadows the [`TryFrom`] implementations of `Isbn10`, so `Isbn10::try_from` only
+    /// accepts an [`Isbn13`]. Use `try_into` instead, which also converts a [`u64`].
     ///
-    /// let isbn_13 = Isbn13::new([9, 7, 8, 1, 4, 9, 2, 0, 6, 7, 6, 6, 5]).unwrap();
-    /// assert_eq!(Isbn10::try_from(isbn_13), "1-4920-6766-0".parse());
-    /// ```
     /// # Errors
     /// If the ISBN13 does not have a 978 prefix, it can not be downcast to an ISBN10, and an
     /// error will be returned.
+    #[deprecated(note = "use `TryFrom<Isbn13>` or `Isbn13::try_into` instead")]
     pub fn try_from(isbn13: Isbn13) -> IsbnResult<Self> {
-        if isbn13.digits[..3] == [9, 7, 8] {
-            let mut a = [0; 10];
-            a[..9].clone_from_slice(&isbn13.digits[3..12]);
-            a[9] = Isbn10::calculate_check_digit(&a);
-            Ok(Isbn10 { digits: a })
-        } else {
-            Err(IsbnError::InvalidConversion)
+        isbn13.try_into()
+    }
+
+    /// Creates a new ISBN10 code from its first 9 digits, calculating the check digit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use isbn::Isbn10;
+    ///
+    /// let isbn10 = Isbn10::from_body([8, 9, 6, 6, 2, 6, 1, 2, 6]).unwrap();
+    /// assert_eq!(isbn10, Isbn10::new([8, 9, 6, 6, 2, 6, 1, 2, 6, 4]).unwrap());
+    ///
+    /// const ISBN10: Isbn10 = match Isbn10::from_body([0, 8, 0, 4, 4, 2, 9, 5, 7]) {
+    ///     Ok(isbn10) => isbn10,
+    ///     Err(_) => panic!("invalid ISBN10"),
+    /// };
+    /// assert_eq!(ISBN10.to_string(), "080442957X");
+    /// ```
+    /// # Errors
+    /// If any of the digits exceed nine, an error will be returned.
+    pub const fn from_body(body: [u8; 9]) -> IsbnResult<Isbn10> {
+        let mut digits = [0; 10];
+        let mut i = 0;
+        while i < 9 {
+            if body[i] > 9 {
+                return Err(IsbnError::DigitTooLarge);
+            }
+            digits[i] = body[i];
+            i += 1;
         }
+        digits[9] = Isbn10::calculate_check_digit(&digits);
+        Ok(Isbn10 { digits })
     }
 
-    fn calculate_check_digit(digits: &[u8; 10]) -> u8 {
-        let sum: usize = digits[..9]
-            .iter()
-            .enumerate()
-            .map(|(i, &d)| d as usize * (10 - i))
-            .sum();
+    /// Creates a new ISBN10 code from a string of its first 9 digits, calculating the check
+    /// digit. Hyphens and spaces are ignored.
+    ///
+    /// ```
+    /// use isbn::Isbn10;
+    ///
+    /// assert_eq!(Isbn10::from_body_str("89-6626-126"), "89-6626-126-4".parse());
+    /// ```
+    /// # Errors
+    /// If the string does not consist of 9 digits, an error will be returned.
+    pub fn from_body_str(s: &str) -> IsbnResult<Isbn10> {
+        let p = Parser::new(s)?;
+        let body = p
+            .digits
+            .as_slice()
+            .try_into()
+            .map_err(|_| IsbnError::InvalidLength)?;
+        Isbn10::from_body(body)
+    }
+
+    const fn calculate_check_digit(digits: &[u8; 10]) -> u8 {
+        let mut sum = 0;
+        let mut i = 0;
+        while i < 9 {
+            sum += digits[i] as usize * (10 - i);
+            i += 1;
+        }
         let sum_m = (sum % 11) as u8;
         if sum_m == 0 { 0 } else { 11 - sum_m }
     }
@@ -351,33 +971,126 @@ impl Isbn10 {
     /// use isbn::Isbn10;
     ///
     /// let isbn_10 = Isbn10::new([8, 9, 6, 6, 2, 6, 1, 2, 6, 4]).unwrap();
-    /// assert_eq!(isbn_10.hyphenate().unwrap().as_str(), "89-6626-126-4");
+    /// assert_eq!(isbn_10.hyphenate().unwrap().as_str(), "89-6626-126-4");
+    /// ```
+    /// # Errors
+    /// If the ISBN is not valid, as determined by the current ISBN rules, an error will be
+    /// returned.
+    pub fn hyphenate(&self) -> Result<ArrayString<17>, IsbnError> {
+        self.trait_hyphenate(&CompiledRanges)
+    }
+
+    /// Display the ISBN in the given style, as with [`Isbn::display_with`].
+    pub fn display_with(&self, style: DisplayStyle) -> DisplayWith<'_> {
+        DisplayWith::new(self.into(), style)
+    }
+
+    /// Look up the groups and rules of the ISBN ranges matching the ISBN, as with
+    /// [`Isbn::lookup_range`].
+    ///
+    /// # Errors
+    /// If the EAN.UCC prefix of the ISBN or its rule is not found, or the rule gives the
+    /// registration group more than five digits, an error will be returned.
+    pub fn lookup_range(&self) -> Result<RangeLookup<'static>, IsbnError> {
+        self.trait_lookup_range(&CompiledRanges)
+    }
+
+    /// Retrieve the name of the registration group.
+    ///
+    /// ```
+    /// use isbn::Isbn10;
+    ///
+    /// let isbn_10 = Isbn10::new([8, 9, 6, 6, 2, 6, 1, 2, 6, 4]).unwrap();
+    /// assert_eq!(isbn_10.registration_group(), Ok("Korea, Republic"));
+    /// ```
+    /// # Errors
+    /// If the ISBN is not valid, as determined by the current ISBN rules, an error will be
+    /// returned.
+    pub fn registration_group(&self) -> Result<&str, IsbnError> {
+        self.trait_registration_group(&CompiledRanges)
+    }
+
+    /// Split an ISBN-10 into its parts.
+    ///
+    /// ```
+    /// use isbn::Isbn10;
+    ///
+    /// let isbn_10 = Isbn10::new([8, 9, 6, 6, 2, 6, 1, 2, 6, 4]).unwrap();
+    /// let parts = isbn_10.parts().unwrap();
+    ///
+    /// assert_eq!(parts.gs1_prefix(), None);
+    /// assert_eq!(parts.registration_group(), "89");
+    /// assert_eq!(parts.registrant(), "6626");
+    /// assert_eq!(parts.publication(), "126");
+    /// assert_eq!(parts.check_digit(), '4');
+    /// assert_eq!(parts.agency(), "Korea, Republic");
+    /// ```
+    /// # Errors
+    /// If the ISBN is not valid, as determined by the current ISBN rules, an error will be
+    /// returned.
+    pub fn parts(&self) -> Result<IsbnParts<'static>, IsbnError> {
+        self.trait_parts(&CompiledRanges)
+    }
+
+    /// Parse an ISBN10 according to the given options.
+    ///
+    /// ```
+    /// use isbn::{Isbn10, ParseOptions};
+    ///
+    /// assert!(Isbn10::parse_with("ISBN-10: 0-8044-2957-X", &ParseOptions::lenient()).is_ok());
+    /// ```
+    /// # Errors
+    /// If the string is not an ISBN10 under the given options, or the checksum is not correct,
+    /// an error will be returned.
+    pub fn parse_with(s: &str, options: &ParseOptions) -> Result<Isbn10, IsbnError> {
+        let p = Parser::with_options(s, options)?;
+        if p.digits.len() == 10 {
+            Ok(p.read_isbn10()?)
+        } else {
+            Err(IsbnError::InvalidLength)
+        }
+    }
+
+    /// The equivalent ISBN13 as a 13-digit number.
+    ///
+    /// ```
+    /// use isbn::Isbn10;
+    ///
+    /// let isbn_10 = Isbn10::new([8, 9, 6, 6, 2, 6, 1, 2, 6, 4]).unwrap();
+    /// assert_eq!(isbn_10.to_u64(), 9788966261260);
+    /// assert_eq!(9788966261260.try_into(), Ok(isbn_10));
     /// ```
-    /// # Errors
-    /// If the ISBN is not valid, as determined by the current ISBN rules, an error will be
-    /// returned.
-    pub fn hyphenate(&self) -> Result<ArrayString<17>, IsbnError> {
-        self.trait_hyphenate()
+    pub fn to_u64(&self) -> u64 {
+        Isbn13::from(*self).to_u64()
     }
 
-    /// Retrieve the name of the registration group.
+    /// Parse an ISBN10, describing where any problem was found.
     ///
-    /// ```
-    /// use isbn::Isbn10;
+    /// # Errors
+    /// If the string is not an ISBN10, an error will be returned.
+    pub fn parse_detailed(s: &str) -> Result<Isbn10, DetailedError> {
+        let p = Parser::new_detailed(s)?;
+        match p.digits.len() {
+            10 => p.read_isbn10(),
+            found => Err(DetailedError::InvalidLength { found }),
+        }
+    }
+
+    /// Split an ISBN-10 into its parts, describing which lookup failed if any.
     ///
-    /// let isbn_10 = Isbn10::new([8, 9, 6, 6, 2, 6, 1, 2, 6, 4]).unwrap();
-    /// assert_eq!(isbn_10.registration_group(), Ok("Korea, Republic"));
-    /// ```
     /// # Errors
     /// If the ISBN is not valid, as determined by the current ISBN rules, an error will be
     /// returned.
-    pub fn registration_group(&self) -> Result<&str, IsbnError> {
-        self.trait_registration_group()
+    pub fn parts_detailed(&self) -> Result<IsbnParts<'static>, DetailedError> {
+        self.trait_parts_detailed(&CompiledRanges)
     }
 }
 
 impl fmt::Display for Isbn10 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            return write!(f, "{}", self.display_with(DisplayStyle::Hyphenated));
+        }
         let mut s = ArrayString::<10>::new();
         self.digits[0..9]
             .iter()
@@ -387,12 +1100,56 @@ impl fmt::Display for Isbn10 {
     }
 }
 
+/// Convert ISBN-13 to ISBN-10, if applicable.
+///
+/// ```
+/// use isbn::{Isbn10, Isbn13};
+///
+/// let isbn_13 = Isbn13::new([9, 7, 8, 1, 4, 9, 2, 0, 6, 7, 6, 6, 5]).unwrap();
+/// let isbn_10: Result<Isbn10, _> = isbn_13.try_into();
+/// assert_eq!(isbn_10, "1-4920-6766-0".parse());
+/// ```
+/// # Errors
+/// If the ISBN13 does not have a 978 prefix, it can not be downcast to an ISBN10, and an
+/// error will be returned.
+impl TryFrom<Isbn13> for Isbn10 {
+    type Error = IsbnError;
+    fn try_from(isbn13: Isbn13) -> Result<Isbn10, IsbnError> {
+        if isbn13.digits[..3] == [9, 7, 8] {
+            let mut a = [0; 10];
+            a[..9].clone_from_slice(&isbn13.digits[3..12]);
+            a[9] = Isbn10::calculate_check_digit(&a);
+            Ok(Isbn10 { digits: a })
+        } else {
+            Err(IsbnError::InvalidConversion)
+        }
+    }
+}
+
+/// Convert the 13-digit number given by [`Isbn10::to_u64`] back to an ISBN10.
+///
+/// As the deprecated inherent `Isbn10::try_from` shadows this implementation, use
+/// `try_into` or write `<Isbn10 as TryFrom<u64>>::try_from`.
+///
+/// ```
+/// use isbn::Isbn10;
+///
+/// let isbn_10 = <Isbn10 as TryFrom<u64>>::try_from(9788966261260).unwrap();
+/// assert_eq!(isbn_10.to_u64(), 9788966261260);
+/// ```
+impl TryFrom<u64> for Isbn10 {
+    type Error = IsbnError;
+    fn try_from(n: u64) -> Result<Isbn10, IsbnError> {
+        Isbn13::try_from(n)?.try_into()
+    }
+}
+
 impl FromStr for Isbn10 {
     type Err = IsbnError;
     fn from_str(s: &str) -> Result<Isbn10, IsbnError> {
-        let mut p = Parser::new(s)?;
+        let p = Parser::new(s)?;
         if p.digits.len() == 10 {
-            p.read_isbn10()
+            Ok(p.read_isbn10()?)
         } else {
             Err(IsbnError::InvalidLength)
         }
@@ -400,8 +1157,7 @@ impl FromStr for Isbn10 {
 }
 
 /// 13-digit ISBN format.
-#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash)]
 pub struct Isbn13 {
     digits: [u8; 13],
 }
@@ -428,22 +1184,26 @@ impl IsbnObject for Isbn13 {
         hyphenated
     }
 
-    fn prefix_element(&self) -> u16 {
-        ((self.digits[0] as u16) << 8) | ((self.digits[1] as u16) << 4) | (self.digits[2] as u16)
+    fn parts_with<'a>(&self, hyphen_at: [usize; 2], agency: &'a str) -> IsbnParts<'a> {
+        let mut digits = ArrayString::new();
+        for &digit in &self.digits {
+            digits.push(convert_isbn_body(digit));
+        }
+
+        IsbnParts {
+            digits,
+            prefix_length: 3,
+            hyphen_at,
+            agency,
+        }
     }
 
-    fn segment(&self, base: usize) -> u32 {
-        (3..9).fold(0, |s, i| {
-            s + u32::from(*self.digits.get(base + i).unwrap_or(&0)) * 10_u32.pow(9 - i as u32)
-        })
+    fn ean_ucc_prefix(&self) -> [u8; 3] {
+        [self.digits[0], self.digits[1], self.digits[2]]
     }
 
-    fn group_prefix(&self, length: usize) -> u32 {
-        let mut digits = 0;
-        for &digit in &self.digits[3..length + 3] {
-            digits = (digits << 4) | digit as u32;
-        }
-        digits
+    fn body(&self) -> &[u8] {
+        &self.digits[3..12]
     }
 }
 
@@ -471,10 +1231,64 @@ impl Isbn13 {
         }
     }
 
-    fn calculate_check_digit(digits: &[u8; 13]) -> u8 {
+    /// Creates a new ISBN13 code from its first 12 digits, calculating the check digit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use isbn::Isbn13;
+    ///
+    /// let isbn13 = Isbn13::from_body([9, 7, 8, 1, 4, 9, 2, 0, 6, 7, 6, 6]).unwrap();
+    /// assert_eq!(isbn13, Isbn13::new([9, 7, 8, 1, 4, 9, 2, 0, 6, 7, 6, 6, 5]).unwrap());
+    ///
+    /// const ISBN13: Isbn13 = match Isbn13::from_body([9, 7, 8, 3, 1, 6, 1, 4, 8, 4, 1, 0]) {
+    ///     Ok(isbn13) => isbn13,
+    ///     Err(_) => panic!("invalid ISBN13"),
+    /// };
+    /// assert_eq!(ISBN13.to_string(), "9783161484100");
+    /// ```
+    /// # Errors
+    /// If any of the digits exceed nine, an error will be returned.
+    pub const fn from_body(body: [u8; 12]) -> IsbnResult<Isbn13> {
+        let mut digits = [0; 13];
+        let mut i = 0;
+        while i < 12 {
+            if body[i] > 9 {
+                return Err(IsbnError::DigitTooLarge);
+            }
+            digits[i] = body[i];
+            i += 1;
+        }
+        digits[12] = Isbn13::calculate_check_digit(&digits);
+        Ok(Isbn13 { digits })
+    }
+
+    /// Creates a new ISBN13 code from a string of its first 12 digits, calculating the check
+    /// digit. Hyphens and spaces are ignored.
+    ///
+    /// ```
+    /// use isbn::Isbn13;
+    ///
+    /// assert_eq!(Isbn13::from_body_str("978-1-4920-6766"), "978-1-4920-6766-5".parse());
+    /// ```
+    /// # Errors
+    /// If the string does not consist of 12 digits, an error will be returned.
+    pub fn from_body_str(s: &str) -> IsbnResult<Isbn13> {
+        let p = Parser::new(s)?;
+        let body = p
+            .digits
+            .as_slice()
+            .try_into()
+            .map_err(|_| IsbnError::InvalidLength)?;
+        Isbn13::from_body(body)
+    }
+
+    const fn calculate_check_digit(digits: &[u8; 13]) -> u8 {
         let mut sum = 0;
-        for i in 0..6 {
-            sum += u16::from(digits[i * 2] + 3 * digits[i * 2 + 1]);
+        let mut i = 0;
+        while i < 6 {
+            sum += (digits[i * 2] + 3 * digits[i * 2 + 1]) as u16;
+            i += 1;
         }
         let sum_m = (sum % 10) as u8;
         if sum_m == 0 { 0 } else { 10 - sum_m }
@@ -498,7 +1312,22 @@ impl Isbn13 {
     /// If the ISBN is not valid, as determined by the current ISBN rules, an error will be
     /// returned.
     pub fn hyphenate(&self) -> Result<ArrayString<17>, IsbnError> {
-        self.trait_hyphenate()
+        self.trait_hyphenate(&CompiledRanges)
+    }
+
+    /// Display the ISBN in the given style, as with [`Isbn::display_with`].
+    pub fn display_with(&self, style: DisplayStyle) -> DisplayWith<'_> {
+        DisplayWith::new(self.into(), style)
+    }
+
+    /// Look up the groups and rules of the ISBN ranges matching the ISBN, as with
+    /// [`Isbn::lookup_range`].
+    ///
+    /// # Errors
+    /// If the EAN.UCC prefix of the ISBN or its rule is not found, or the rule gives the
+    /// registration group more than five digits, an error will be returned.
+    pub fn lookup_range(&self) -> Result<RangeLookup<'static>, IsbnError> {
+        self.trait_lookup_range(&CompiledRanges)
     }
 
     /// Retrieve the name of the registration group.
@@ -513,12 +1342,96 @@ impl Isbn13 {
     /// If the ISBN is not valid, as determined by the current ISBN rules, an error will be
     /// returned.
     pub fn registration_group(&self) -> Result<&str, IsbnError> {
-        self.trait_registration_group()
+        self.trait_registration_group(&CompiledRanges)
+    }
+
+    /// Split an ISBN-13 into its parts.
+    ///
+    /// ```
+    /// use isbn::Isbn13;
+    ///
+    /// let isbn_13 = Isbn13::new([9, 7, 8, 1, 4, 9, 2, 0, 6, 7, 6, 6, 5]).unwrap();
+    /// let parts = isbn_13.parts().unwrap();
+    ///
+    /// assert_eq!(parts.gs1_prefix(), Some("978"));
+    /// assert_eq!(parts.registration_group(), "1");
+    /// assert_eq!(parts.registrant(), "4920");
+    /// assert_eq!(parts.publication(), "6766");
+    /// assert_eq!(parts.check_digit(), '5');
+    /// assert_eq!(parts.agency(), "English language");
+    /// ```
+    /// # Errors
+    /// If the ISBN is not valid, as determined by the current ISBN rules, an error will be
+    /// returned.
+    pub fn parts(&self) -> Result<IsbnParts<'static>, IsbnError> {
+        self.trait_parts(&CompiledRanges)
+    }
+
+    /// Parse an ISBN13 according to the given options.
+    ///
+    /// ```
+    /// use isbn::{Isbn13, ParseOptions};
+    ///
+    /// assert!(Isbn13::parse_with("ISBN-13: 978-3-16-148410-0", &ParseOptions::lenient()).is_ok());
+    /// ```
+    /// # Errors
+    /// If the string is not an ISBN13 under the given options, or the checksum is not correct,
+    /// an error will be returned.
+    pub fn parse_with(s: &str, options: &ParseOptions) -> Result<Isbn13, IsbnError> {
+        let p = Parser::with_options(s, options)?;
+        if p.digits.len() == 13 {
+            Ok(p.read_isbn13()?)
+        } else {
+            Err(IsbnError::InvalidLength)
+        }
+    }
+
+    /// The ISBN as a 13-digit number.
+    ///
+    /// ```
+    /// use isbn::Isbn13;
+    ///
+    /// let isbn_13 = Isbn13::new([9, 7, 8, 1, 4, 9, 2, 0, 6, 7, 6, 6, 5]).unwrap();
+    /// assert_eq!(isbn_13.to_u64(), 9781492067665);
+    /// assert_eq!(Isbn13::try_from(9781492067665), Ok(isbn_13));
+    /// ```
+    pub const fn to_u64(&self) -> u64 {
+        let mut n = 0;
+        let mut i = 0;
+        while i < 13 {
+            n = n * 10 + self.digits[i] as u64;
+            i += 1;
+        }
+        n
+    }
+
+    /// Parse an ISBN13, describing where any problem was found.
+    ///
+    /// # Errors
+    /// If the string is not an ISBN13, an error will be returned.
+    pub fn parse_detailed(s: &str) -> Result<Isbn13, DetailedError> {
+        let p = Parser::new_detailed(s)?;
+        match p.digits.len() {
+            13 => p.read_isbn13(),
+            found => Err(DetailedError::InvalidLength { found }),
+        }
+    }
+
+    /// Split an ISBN-13 into its parts, describing which lookup failed if any.
+    ///
+    /// # Errors
+    /// If the ISBN is not valid, as determined by the current ISBN rules, an error will be
+    /// returned.
+    pub fn parts_detailed(&self) -> Result<IsbnParts<'static>, DetailedError> {
+        self.trait_parts_detailed(&CompiledRanges)
     }
 }
 
 impl fmt::Display for Isbn13 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            return write!(f, "{}", self.display_with(DisplayStyle::Hyphenated));
+        }
         let mut s = ArrayString::<13>::new();
         self.digits
             .iter()
@@ -537,12 +1450,33 @@ impl From<Isbn10> for Isbn13 {
     }
 }
 
+impl TryFrom<u64> for Isbn13 {
+    type Error = IsbnError;
+    fn try_from(mut n: u64) -> Result<Isbn13, IsbnError> {
+        if n >= 10_u64.pow(13) {
+            return Err(IsbnError::InvalidLength);
+        }
+        let mut digits = [0; 13];
+        for digit in digits.iter_mut().rev() {
+            *digit = (n % 10) as u8;
+            n /= 10;
+        }
+        Isbn13::new(digits)
+    }
+}
+
+impl From<Isbn> for Isbn13 {
+    fn from(isbn: Isbn) -> Isbn13 {
+        isbn.canonical()
+    }
+}
+
 impl FromStr for Isbn13 {
     type Err = IsbnError;
     fn from_str(s: &str) -> Result<Isbn13, IsbnError> {
-        let mut p = Parser::new(s)?;
+        let p = Parser::new(s)?;
         if p.digits.len() == 13 {
-            p.read_isbn13()
+            Ok(p.read_isbn13()?)
         } else {
             Err(IsbnError::InvalidLength)
         }
@@ -550,7 +1484,10 @@ impl FromStr for Isbn13 {
 }
 
 /// An error which can be returned when parsing an ISBN.
-#[derive(Debug, PartialEq)]
+///
+/// See [`DetailedError`] for an error which also describes where the problem was found.
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+#[non_exhaustive]
 pub enum IsbnError {
     /// The given string is too short or too long to be an ISBN.
     InvalidLength,
@@ -566,6 +1503,11 @@ pub enum IsbnError {
     InvalidConversion,
     /// One or supplied more digits were too large.
     DigitTooLarge,
+    /// The hyphens do not separate the elements given by the ISBN ranges. Contains the
+    /// correctly hyphenated ISBN.
+    InvalidHyphenation(ArrayString<17>),
+    /// The registrant element does not have the length given by the ISBN ranges.
+    InvalidRegistrant,
 }
 
 impl fmt::Display for IsbnError {
@@ -586,10 +1528,119 @@ impl fmt::Display for IsbnError {
                 f,
                 "A supplied digit was larger than 9, or the ISBN10 check digit was larger than 10."
             ),
+            IsbnError::InvalidRegistrant => write!(
+                f,
+                "The registrant does not have the length given by the ISBN ranges."
+            ),
+            IsbnError::InvalidHyphenation(expected) => {
+                write!(f, "Misplaced hyphens, expected {}.", expected)
+            }
+        }
+    }
+}
+
+impl core::error::Error for IsbnError {}
+
+/// An element of an ISBN whose length is looked up in the ISBN ranges.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub enum RangeElement {
+    /// The registration group, looked up in the rules of the GS1 prefix.
+    RegistrationGroup,
+    /// The registrant, looked up in the rules of the registration group.
+    Registrant,
+}
+
+/// An error which describes where a problem with an ISBN was found, returned by
+/// [`Isbn::parse_detailed`] and [`Isbn::parts_detailed`].
+///
+/// # Examples
+///
+/// ```
+/// use isbn::{DetailedError, Isbn, IsbnError};
+///
+/// assert_eq!(
+///     Isbn::parse_detailed("978-3-16-14841O-0"),
+///     Err(DetailedError::InvalidChar { found: 'O', offset: 14 })
+/// );
+/// assert_eq!(
+///     Isbn::parse_detailed("978-3-16-148410-1"),
+///     Err(DetailedError::InvalidChecksum { expected: '0', found: '1' })
+/// );
+/// assert_eq!(
+///     IsbnError::from(Isbn::parse_detailed("978-3-16-14841").unwrap_err()),
+///     IsbnError::InvalidLength
+/// );
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+#[non_exhaustive]
+pub enum DetailedError {
+    /// Encountered a character which can not appear at this point of an ISBN, at the given
+    /// byte offset.
+    InvalidChar { found: char, offset: usize },
+    /// Found the given number of digits, which is not the length of an ISBN.
+    InvalidLength { found: usize },
+    /// The check digit does not match the one calculated from the other digits.
+    InvalidChecksum { expected: char, found: char },
+    /// Failed to look up the length of an element in the rules for the given prefix, such as
+    /// `978` for the registration group or `978-89` for the registrant.
+    Lookup {
+        element: RangeElement,
+        prefix: ArrayString<9>,
+        error: IsbnError,
+    },
+}
+
+impl DetailedError {
+    /// The kind of error, without any details.
+    pub fn kind(&self) -> IsbnError {
+        match self {
+            DetailedError::InvalidChar { .. } => IsbnError::InvalidDigit,
+            DetailedError::InvalidLength { .. } => IsbnError::InvalidLength,
+            DetailedError::InvalidChecksum { .. } => IsbnError::InvalidChecksum,
+            DetailedError::Lookup { error, .. } => *error,
+        }
+    }
+}
+
+impl fmt::Display for DetailedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DetailedError::InvalidChar { found, offset } => {
+                write!(
+                    f,
+                    "Encountered an invalid character {found:?} at byte {offset}."
+                )
+            }
+            DetailedError::InvalidLength { found } => {
+                write!(f, "Found {found} digits, but an ISBN has 10 or 13 digits.")
+            }
+            DetailedError::InvalidChecksum { expected, found } => write!(
+                f,
+                "Failed to validate checksum, expected check digit {expected} but found {found}."
+            ),
+            DetailedError::Lookup {
+                element,
+                prefix,
+                error,
+            } => {
+                let element = match element {
+                    RangeElement::RegistrationGroup => "registration group",
+                    RangeElement::Registrant => "registrant",
+                };
+                write!(f, "Failed to look up the {element} after {prefix}: {error}")
+            }
         }
     }
 }
 
+impl core::error::Error for DetailedError {}
+
+impl From<DetailedError> for IsbnError {
+    fn from(e: DetailedError) -> Self {
+        e.kind()
+    }
+}
+
 impl From<ParseIntError> for IsbnError {
     fn from(_: ParseIntError) -> Self {
         IsbnError::InvalidDigit
@@ -602,75 +1653,102 @@ impl From<CapacityError<u8>> for IsbnError {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 struct Parser {
     digits: ArrayVec<u8, 13>,
+    has_x: bool,
 }
 
 impl Parser {
     pub fn new<S: AsRef<str>>(s: S) -> Result<Parser, IsbnError> {
-        let mut digits = ArrayVec::new();
-        let mut has_x = false;
+        Ok(Parser::new_detailed(s.as_ref())?)
+    }
 
-        for c in s.as_ref().chars() {
+    fn new_detailed(s: &str) -> Result<Parser, DetailedError> {
+        let mut parser = Parser::default();
+        let mut found = 0;
+
+        for (offset, c) in s.char_indices() {
             match c {
                 '-' | ' ' => {}
-                'X' | 'x' => {
-                    if digits.len() == 9 {
-                        has_x = true;
-                        digits.push(10);
-                    } else {
-                        return Err(IsbnError::InvalidDigit);
-                    }
+                '0'..='9' if parser.digits.is_full() => found += 1,
+                _ => {
+                    parser
+                        .push(c)
+                        .map_err(|_| DetailedError::InvalidChar { found: c, offset })?;
+                    found += 1;
                 }
-                '0'..='9' => {
-                    if has_x {
-                        return Err(IsbnError::InvalidDigit);
-                    } else {
-                        digits.try_push(c.to_digit(10).unwrap() as u8)?
-                    }
+            }
+        }
+        if found > parser.digits.len() {
+            Err(DetailedError::InvalidLength { found })
+        } else {
+            Ok(parser)
+        }
+    }
+
+    /// Reads a single digit, where `X` is only accepted as the check digit of an ISBN10.
+    fn push(&mut self, c: char) -> Result<(), IsbnError> {
+        match c {
+            'X' | 'x' if self.digits.len() == 9 => {
+                self.has_x = true;
+                self.digits.push(10);
+            }
+            '0'..='9' => {
+                if self.has_x {
+                    return Err(IsbnError::InvalidDigit);
+                } else {
+                    self.digits.try_push(c.to_digit(10).unwrap() as u8)?
                 }
-                _ => return Err(IsbnError::InvalidDigit),
             }
+            _ => return Err(IsbnError::InvalidDigit),
         }
-        Ok(Parser { digits })
+        Ok(())
     }
 
-    fn read_isbn(&mut self) -> Result<Isbn, IsbnError> {
+    fn read_isbn(&self) -> Result<Isbn, DetailedError> {
         match self.digits.len() {
             10 => self.read_isbn10().map(Isbn::_10),
             13 => self.read_isbn13().map(Isbn::_13),
-            _ => Err(IsbnError::InvalidLength),
+            found => Err(DetailedError::InvalidLength { found }),
         }
     }
 
     /// Reads an ISBN13 from self. Requires that length is checked beforehand.
-    fn read_isbn13(&mut self) -> Result<Isbn13, IsbnError> {
+    fn read_isbn13(&self) -> Result<Isbn13, DetailedError> {
         let mut digits = [0; 13];
         digits.clone_from_slice(&self.digits);
         let check_digit = Isbn13::calculate_check_digit(&digits);
         if check_digit == digits[12] {
             Ok(Isbn13 { digits })
         } else {
-            Err(IsbnError::InvalidDigit)
+            Err(DetailedError::InvalidChecksum {
+                expected: convert_isbn_body(check_digit),
+                found: convert_isbn_body(digits[12]),
+            })
         }
     }
 
     /// Reads an ISBN10 from self. Requires that length is checked beforehand.
-    fn read_isbn10(&mut self) -> Result<Isbn10, IsbnError> {
+    fn read_isbn10(&self) -> Result<Isbn10, DetailedError> {
         let mut digits = [0; 10];
         digits.clone_from_slice(&self.digits);
         let check_digit = Isbn10::calculate_check_digit(&digits);
         if check_digit == digits[9] {
             Ok(Isbn10 { digits })
         } else {
-            Err(IsbnError::InvalidDigit)
+            Err(DetailedError::InvalidChecksum {
+                expected: convert_isbn10_check(check_digit),
+                found: convert_isbn10_check(digits[9]),
+            })
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use core::fmt::Write;
+
     use super::*;
 
     #[test]
@@ -731,6 +1809,249 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parts_match_hyphenation() {
+        for s in [
+            "0-306-40615-2",
+            "99921-58-10-7",
+            "9971-5-0210-0",
+            "0-9752298-0-X",
+            "978-3-16-148410-0",
+        ] {
+            let isbn = Isbn::from_str(s).unwrap();
+            let parts = isbn.parts().unwrap();
+            assert_eq!(parts.hyphenate(), isbn.hyphenate().unwrap());
+            assert_eq!(parts.hyphenate().as_str(), s);
+        }
+    }
+
+    #[test]
+    fn test_parse_hyphenated() {
+        for s in [
+            "0-306-40615-2",
+            "0 306 40615 2",
+            "0-9752298-0-x",
+            "978-3-16-148410-0",
+        ] {
+            assert_eq!(Isbn::parse_hyphenated(s), Isbn::from_str(s));
+        }
+        for s in ["03-06-40615-2", "0-306-40615 -2", "9783-16-148410-0"] {
+            assert!(matches!(
+                Isbn::parse_hyphenated(s),
+                Err(IsbnError::InvalidHyphenation(_))
+            ));
+        }
+        assert_eq!(
+            Isbn::parse_hyphenated("979-0-000-00000-1"),
+            Err(IsbnError::UndefinedRange)
+        );
+    }
+
+    #[test]
+    fn test_from_body() {
+        for s in ["0-306-40615-2", "0-9752298-0-X", "978-3-16-148410-0"] {
+            let isbn = Isbn::from_str(s).unwrap();
+            let body = &s[..s.len() - 2];
+            match isbn {
+                Isbn::_10(isbn) => assert_eq!(Isbn10::from_body_str(body), Ok(isbn)),
+                Isbn::_13(isbn) => assert_eq!(Isbn13::from_body_str(body), Ok(isbn)),
+            }
+        }
+        assert_eq!(
+            Isbn10::from_body([0, 3, 0, 6, 4, 0, 6, 1, 10]),
+            Err(IsbnError::DigitTooLarge)
+        );
+        assert_eq!(
+            Isbn10::from_body_str("0-306-4061"),
+            Err(IsbnError::InvalidLength)
+        );
+        assert_eq!(
+            Isbn13::from_body_str("978-3-16-14841X"),
+            Err(IsbnError::InvalidDigit)
+        );
+    }
+
+    #[test]
+    fn test_u64_round_trip() {
+        for s in [
+            "0-306-40615-2",
+            "0-9752298-0-X",
+            "978-3-16-148410-0",
+            "979-10-90636-07-1",
+        ] {
+            let isbn = Isbn::from_str(s).unwrap();
+            let n = isbn.to_u64();
+            match isbn {
+                Isbn::_10(isbn) => assert_eq!(n.try_into(), Ok(isbn)),
+                Isbn::_13(isbn) => assert_eq!(Isbn13::try_from(n), Ok(isbn)),
+            }
+        }
+        assert_eq!(
+            Isbn13::try_from(97831614841000),
+            Err(IsbnError::InvalidLength)
+        );
+        assert_eq!(
+            Isbn13::try_from(9783161484101),
+            Err(IsbnError::InvalidChecksum)
+        );
+        assert_eq!(
+            <Isbn10 as TryFrom<u64>>::try_from(9791090636071),
+            Err(IsbnError::InvalidConversion)
+        );
+    }
+
+    #[test]
+    fn test_ord_matches_u64() {
+        let isbns = [
+            "0-306-40615-2",
+            "978-0-306-40615-7",
+            "0-9752298-0-X",
+            "979-10-90636-07-1",
+            "978-3-16-148410-0",
+        ]
+        .map(|s| Isbn::from_str(s).unwrap());
+        for a in &isbns {
+            for b in &isbns {
+                let key = |isbn: &Isbn| (isbn.to_u64(), matches!(isbn, Isbn::_13(_)));
+                assert_eq!(a.cmp(b), key(a).cmp(&key(b)));
+                assert_eq!(a.cmp(b) == Ordering::Equal, a == b);
+            }
+        }
+        assert!(isbns[0] < isbns[1]);
+    }
+
+    #[test]
+    fn test_segments_match() {
+        let isbn_10 = Isbn10::from_str("89-6626-126-4").unwrap();
+        let isbn_13 = Isbn13::from(isbn_10);
+        for base in 0..=5 {
+            assert_eq!(isbn_10.segment(base), isbn_13.segment(base));
+        }
+        assert_eq!(isbn_13.segment(0), 8_966_261);
+        assert_eq!(isbn_13.segment(2), 6_626_126);
+        assert_eq!(isbn_13.segment(5), 6_126_000);
+    }
+
+    #[test]
+    fn test_canonical() {
+        let pairs = [
+            ("0-306-40615-2", "978-0-306-40615-7"),
+            ("0-9752298-0-X", "978-0-9752298-0-4"),
+        ];
+        for (isbn_10, isbn_13) in pairs {
+            let isbn_10 = Isbn::from_str(isbn_10).unwrap();
+            let isbn_13 = Isbn::from_str(isbn_13).unwrap();
+            assert!(isbn_10.same_book(&isbn_13));
+            assert_eq!(isbn_10.canonical(), isbn_13.canonical());
+            assert_eq!(Isbn13::from(isbn_10.clone()), isbn_13.canonical());
+            assert_eq!(isbn_10.canonical().to_u64(), isbn_10.to_u64());
+        }
+        let a = Isbn::from_str("0-306-40615-2").unwrap();
+        let b = Isbn::from_str("0-9752298-0-X").unwrap();
+        assert!(!a.same_book(&b));
+    }
+
+    #[test]
+    fn test_locale() {
+        let isbn = Isbn::from_str("89-6626-126-4").unwrap();
+        let locale = isbn.parts().unwrap().locale().unwrap();
+        assert_eq!(locale.countries(), ["KR"]);
+        assert_eq!(locale.languages(), ["ko"]);
+
+        let isbn = Isbn::from_str("978-80-7203-000-2").unwrap();
+        let locale = isbn.parts().unwrap().locale().unwrap();
+        assert_eq!(locale.countries(), ["CZ", "SK"]);
+        assert!(locale.is_multi_region());
+        assert_eq!(Isbn::ean_ucc_groups().next().unwrap().locale(), None);
+    }
+
+    #[test]
+    fn test_display_styles() {
+        let isbn_10 = Isbn10::from_str("89-6626-126-4").unwrap();
+        let isbn_13 = Isbn13::from_str("978-1-4920-6766-5").unwrap();
+        let undefined = Isbn13::from_str("979-0-000-00000-1").unwrap();
+        let mut s = ArrayString::<64>::new();
+        write!(s, "{:#} {:#} {:#}", isbn_10, Isbn::_13(isbn_13), undefined).unwrap();
+        assert_eq!(s.as_str(), "89-6626-126-4 978-1-4920-6766-5 9790000000001");
+
+        for (style, expected) in [
+            (DisplayStyle::Compact, "9781492067665"),
+            (DisplayStyle::Hyphenated, "978-1-4920-6766-5"),
+            (DisplayStyle::Spaced, "978 1 4920 6766 5"),
+            (DisplayStyle::Prefixed, "ISBN 978-1-4920-6766-5"),
+            (DisplayStyle::Labelled, "ISBN-13: 978-1-4920-6766-5"),
+        ] {
+            s.clear();
+            write!(s, "{}", isbn_13.display_with(style)).unwrap();
+            assert_eq!(s.as_str(), expected);
+        }
+
+        s.clear();
+        write!(s, "{}", undefined.display_with(DisplayStyle::Labelled)).unwrap();
+        assert_eq!(s.as_str(), "ISBN-13: 9790000000001");
+    }
+
+    #[test]
+    fn test_detailed_errors() {
+        assert_eq!(
+            Isbn::parse_detailed("0-306-40615-3"),
+            Err(DetailedError::InvalidChecksum {
+                expected: '2',
+                found: '3'
+            })
+        );
+        assert_eq!(
+            Isbn::parse_detailed("0-8044-2957-1"),
+            Err(DetailedError::InvalidChecksum {
+                expected: 'X',
+                found: '1'
+            })
+        );
+        assert_eq!(
+            Isbn::parse_detailed("0-8044-X957-1"),
+            Err(DetailedError::InvalidChar {
+                found: 'X',
+                offset: 7
+            })
+        );
+        assert_eq!(
+            Isbn::parse_detailed("978-0-306-40615-7-12"),
+            Err(DetailedError::InvalidLength { found: 15 })
+        );
+        assert_eq!(
+            Isbn10::parse_detailed("978-0-306-40615-7"),
+            Err(DetailedError::InvalidLength { found: 13 })
+        );
+        assert_eq!(
+            Isbn::from_str("0-306-40615-3"),
+            Err(IsbnError::InvalidChecksum)
+        );
+
+        let isbn = Isbn::from_str("979-0-000-00000-1").unwrap();
+        assert_eq!(
+            isbn.parts_detailed(),
+            Err(DetailedError::Lookup {
+                element: RangeElement::RegistrationGroup,
+                prefix: ArrayString::from("979").unwrap(),
+                error: IsbnError::UndefinedRange,
+            })
+        );
+        assert_eq!(isbn.parts(), Err(IsbnError::UndefinedRange));
+
+        let mut s = ArrayString::<128>::new();
+        write!(
+            s,
+            "{}",
+            DetailedError::Lookup {
+                element: RangeElement::Registrant,
+                prefix: ArrayString::from("978-80").unwrap(),
+                error: IsbnError::UndefinedRange,
+            }
+        )
+        .unwrap();
+        assert!(s.starts_with("Failed to look up the registrant after 978-80: "));
+    }
+
     #[test]
     fn test_from_str_isbn13() {
         // Wikipedia Example
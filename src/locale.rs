@@ -0,0 +1,41 @@
+/// The countries and languages of a registration group, from a curated mapping which is
+/// maintained alongside the ISBN ranges.
+///
+/// # Examples
+///
+/// ```
+/// use isbn::Isbn13;
+///
+/// let isbn_13: Isbn13 = "978-89-6626-126-0".parse().unwrap();
+/// let locale = isbn_13.parts().unwrap().locale().unwrap();
+///
+/// assert_eq!(locale.countries(), ["KR"]);
+/// assert_eq!(locale.languages(), ["ko"]);
+/// assert!(!locale.is_multi_region());
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub struct GroupLocale<'a> {
+    pub(crate) countries: &'a [&'a str],
+    pub(crate) languages: &'a [&'a str],
+    pub(crate) multi_region: bool,
+}
+
+impl<'a> GroupLocale<'a> {
+    /// The ISO 3166-1 alpha-2 codes of the countries using the registration group, such as
+    /// `KR`. Empty for groups not tied to any country.
+    pub fn countries(&self) -> &'a [&'a str] {
+        self.countries
+    }
+
+    /// The ISO 639-1 codes of the main languages published under the registration group, such
+    /// as `ko`. Empty for groups not tied to any language.
+    pub fn languages(&self) -> &'a [&'a str] {
+        self.languages
+    }
+
+    /// Whether the registration group spans several countries or regions, such as the English
+    /// language groups.
+    pub fn is_multi_region(&self) -> bool {
+        self.multi_region
+    }
+}
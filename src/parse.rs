@@ -0,0 +1,256 @@
+use crate::{IsbnError, Parser};
+
+/// Separators accepted by [`ParseOptions::lenient`].
+const LENIENT_SEPARATORS: &[char] = &[
+    '-', ' ', '.', '_', '\t', '\u{a0}', '\u{2009}', '\u{202f}', '\u{3000}', '\u{2010}', '\u{2011}',
+    '\u{2012}', '\u{2013}', '\u{2014}', '\u{2015}', '\u{2212}', '\u{fe63}', '\u{ff0d}',
+];
+
+/// The zero digit of each run of Unicode decimal digits accepted by
+/// [`ParseOptions::unicode_digits`].
+const UNICODE_ZEROS: &[u32] = &[
+    0x0660, 0x06f0, 0x07c0, 0x0966, 0x09e6, 0x0a66, 0x0ae6, 0x0b66, 0x0be6, 0x0c66, 0x0ce6, 0x0d66,
+    0x0de6, 0x0e50, 0x0ed0, 0x0f20, 0x1040, 0x1090, 0x17e0, 0x1810, 0x1946, 0x19d0, 0x1a80, 0x1a90,
+    0x1b50, 0x1bb0, 0x1c40, 0x1c50, 0xa620, 0xa8d0, 0xa900, 0xa9d0, 0xa9f0, 0xaa50, 0xabf0, 0xff10,
+    0x1d7ce, 0x1d7d8, 0x1d7e2, 0x1d7ec, 0x1d7f6,
+];
+
+/// Options for parsing ISBNs from less tidy sources, used by [`Isbn::parse_with`],
+/// [`Isbn10::parse_with`] and [`Isbn13::parse_with`].
+///
+/// [`ParseOptions::strict`] accepts the same strings as [`str::parse`], while
+/// [`ParseOptions::lenient`] accepts everything the individual options allow.
+///
+/// # Examples
+///
+/// ```
+/// use isbn::{Isbn13, ParseOptions};
+///
+/// let isbn: Isbn13 = "978-3-16-148410-0".parse().unwrap();
+/// let lenient = ParseOptions::lenient();
+///
+/// let dashed = "ISBN 978\u{2010}3\u{2010}16\u{2010}148410\u{2010}0";
+/// assert_eq!(Isbn13::parse_with(dashed, &lenient), Ok(isbn));
+/// assert_eq!(Isbn13::parse_with("urn:isbn:９７８３１６１４８４１００", &lenient), Ok(isbn));
+///
+/// let dotted = ParseOptions::strict().separators(&['.']);
+/// assert_eq!(Isbn13::parse_with("978.3.16.148410.0", &dotted), Ok(isbn));
+/// ```
+///
+/// [`Isbn::parse_with`]: crate::Isbn::parse_with
+/// [`Isbn10::parse_with`]: crate::Isbn10::parse_with
+/// [`Isbn13::parse_with`]: crate::Isbn13::parse_with
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+pub struct ParseOptions<'a> {
+    separators: &'a [char],
+    prefixes: bool,
+    unicode_digits: bool,
+}
+
+impl ParseOptions<'static> {
+    /// Only ASCII digits, separated by `-` or ` `.
+    pub const fn strict() -> Self {
+        ParseOptions {
+            separators: &['-', ' '],
+            prefixes: false,
+            unicode_digits: false,
+        }
+    }
+
+    /// Unicode digits, separated by hyphens, Unicode dashes, spaces, dots or underscores, with
+    /// an optional `ISBN` or `urn:isbn:` prefix.
+    pub const fn lenient() -> Self {
+        ParseOptions {
+            separators: LENIENT_SEPARATORS,
+            prefixes: true,
+            unicode_digits: true,
+        }
+    }
+}
+
+impl<'a> ParseOptions<'a> {
+    /// Set the characters which may appear between digits.
+    pub const fn separators(self, separators: &[char]) -> ParseOptions<'_> {
+        ParseOptions {
+            separators,
+            prefixes: self.prefixes,
+            unicode_digits: self.unicode_digits,
+        }
+    }
+
+    /// Accept a leading `urn:isbn:`, `ISBN`, `ISBN:`, `ISBN-10:` or `ISBN-13:`, in any case and
+    /// with any whitespace around the length and colon, such as `ISBN 13: `. A length in the
+    /// prefix must match the length of the ISBN.
+    pub const fn prefixes(mut self, prefixes: bool) -> Self {
+        self.prefixes = prefixes;
+        self
+    }
+
+    /// Accept decimal digits from any script, such as full-width digits.
+    pub const fn unicode_digits(mut self, unicode_digits: bool) -> Self {
+        self.unicode_digits = unicode_digits;
+        self
+    }
+
+    /// Strips a recognized prefix, returning the rest of the string and the length named by
+    /// the prefix, if any.
+    fn strip_prefix<'s>(&self, s: &'s str) -> (&'s str, Option<usize>) {
+        if !self.prefixes {
+            return (s, None);
+        }
+
+        let s = s.trim_start();
+        if let Some(rest) = strip_prefix_ignore_case(s, "urn:isbn:") {
+            return (rest, None);
+        }
+        let Some(rest) = strip_prefix_ignore_case(s, "isbn") else {
+            return (s, None);
+        };
+        let rest = rest.strip_prefix('-').unwrap_or(rest).trim_start();
+        // A length is only a label if it is followed by a colon or whitespace, so the digits of
+        // `ISBN1000000009` are not mistaken for one.
+        let label = [("10", 10), ("13", 13)]
+            .into_iter()
+            .find_map(|(label, length)| {
+                let rest = rest.strip_prefix(label)?;
+                (rest.starts_with(':') || rest.starts_with(char::is_whitespace))
+                    .then(|| (rest.trim_start(), length))
+            });
+        let (rest, length) = label.map_or((rest, None), |(rest, length)| (rest, Some(length)));
+        let rest = rest.strip_prefix(':').map_or(rest, str::trim_start);
+        (rest, length)
+    }
+
+    /// Maps `c` to the ASCII digit or `X` it stands for.
+    fn normalize(&self, c: char) -> char {
+        if !self.unicode_digits || c.is_ascii() {
+            return c;
+        }
+        match c {
+            '\u{ff38}' | '\u{ff58}' => 'X',
+            _ => UNICODE_ZEROS
+                .iter()
+                .find_map(|&zero| {
+                    let digit = (c as u32).checked_sub(zero).filter(|&d| d < 10)?;
+                    char::from_digit(digit, 10)
+                })
+                .unwrap_or(c),
+        }
+    }
+}
+
+impl Default for ParseOptions<'static> {
+    fn default() -> Self {
+        ParseOptions::strict()
+    }
+}
+
+fn strip_prefix_ignore_case<'s>(s: &'s str, prefix: &str) -> Option<&'s str> {
+    let head = s.get(..prefix.len())?;
+    if head.eq_ignore_ascii_case(prefix) {
+        Some(&s[prefix.len()..])
+    } else {
+        None
+    }
+}
+
+impl Parser {
+    pub fn with_options(s: &str, options: &ParseOptions) -> Result<Parser, IsbnError> {
+        let (s, length) = options.strip_prefix(s);
+        let mut parser = Parser::default();
+
+        for c in s.chars() {
+            if options.separators.contains(&c) {
+                continue;
+            }
+            parser.push(options.normalize(c))?;
+        }
+
+        match length {
+            Some(length) if length != parser.digits.len() => Err(IsbnError::InvalidLength),
+            _ => Ok(parser),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Isbn, Isbn10, Isbn13};
+
+    #[test]
+    fn test_strict_matches_from_str() {
+        let strict = ParseOptions::strict();
+        for s in ["978-3-16-148410-0", "0 306 40615 2", "0-9752298-0-x"] {
+            assert_eq!(Isbn::parse_with(s, &strict), s.parse());
+        }
+        for s in [
+            "ISBN 978-3-16-148410-0",
+            "978\u{2013}3\u{2013}16\u{2013}148410\u{2013}0",
+        ] {
+            assert_eq!(Isbn::parse_with(s, &strict), Err(IsbnError::InvalidDigit));
+        }
+    }
+
+    #[test]
+    fn test_lenient() {
+        let lenient = ParseOptions::lenient();
+        let isbn_13: Isbn13 = "9783161484100".parse().unwrap();
+        for s in [
+            "ISBN 978\u{2010}3\u{2010}16\u{2010}148410\u{2010}0",
+            "isbn-13: 978.3.16.148410.0",
+            "  URN:ISBN:978_3_16_148410_0",
+            "ISBN:９７８－３－１６－１４８４１０－０",
+        ] {
+            assert_eq!(Isbn13::parse_with(s, &lenient), Ok(isbn_13));
+        }
+
+        let isbn_10: Isbn10 = "080442957X".parse().unwrap();
+        assert_eq!(
+            Isbn10::parse_with("ISBN-10 0-8044-2957-Ｘ", &lenient),
+            Ok(isbn_10)
+        );
+        assert_eq!(
+            Isbn::parse_with("ISBN-10: 978-3-16-148410-0", &lenient),
+            Err(IsbnError::InvalidLength)
+        );
+
+        assert_eq!(
+            Isbn13::parse_with("ISBN 13: 978-3-16-148410-0", &lenient),
+            Ok(isbn_13)
+        );
+        let isbn_10: Isbn10 = "0306406152".parse().unwrap();
+        assert_eq!(
+            Isbn10::parse_with("ISBN 10: 0-306-40615-2", &lenient),
+            Ok(isbn_10)
+        );
+        assert_eq!(
+            Isbn10::parse_with("ISBN 10 0-306-40615-2", &lenient),
+            Ok(isbn_10)
+        );
+        // The leading digits of the ISBN are not a length.
+        let isbn_10: Isbn10 = "1000000001".parse().unwrap();
+        assert_eq!(Isbn10::parse_with("ISBN1000000001", &lenient), Ok(isbn_10));
+        assert_eq!(Isbn10::parse_with("ISBN:1000000001", &lenient), Ok(isbn_10));
+        assert_eq!(
+            Isbn::parse_with("ISBN1000000009", &lenient),
+            Err(IsbnError::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn test_individual_options() {
+        let options = ParseOptions::strict().unicode_digits(true);
+        assert!(Isbn::parse_with("٠-٣٠٦-٤٠٦١٥-٢", &options).is_ok());
+        assert!(Isbn::parse_with("ISBN 0-306-40615-2", &options).is_err());
+
+        let options = ParseOptions::strict().prefixes(true).separators(&[]);
+        assert!(Isbn::parse_with("ISBN0306406152", &options).is_ok());
+        assert!(Isbn::parse_with("ISBN 0306406152", &options).is_ok());
+        assert!(Isbn::parse_with("ISBN 030 6406152", &options).is_err());
+
+        let options = ParseOptions::strict().separators(&['-']).prefixes(true);
+        assert!(Isbn::parse_with("ISBN 978-3-16-148410-0", &options).is_ok());
+        assert!(Isbn::parse_with("ISBN-13: 978-3-16-148410-0", &options).is_ok());
+    }
+}
@@ -1,20 +1,36 @@
+mod diff;
+#[cfg(feature = "serialize")]
+mod serialize;
+mod store;
+mod validate;
+mod write;
+
+pub use diff::{RangeDiff, RuleChange};
+pub use store::{IsbnRangeStore, RangeWatcher};
+pub use validate::RangeIssue;
+
 use std::fs::File;
-use std::hash::RandomState;
-use std::io::{BufRead, BufReader};
-use std::num::NonZeroUsize;
+use std::hash::{Hash, RandomState};
+use std::io::{self, BufRead, BufReader, Read};
 use std::path::Path;
 use std::str::FromStr;
+use std::{error, fmt, mem};
 
 use arrayvec::ArrayString;
 use indexmap::IndexMap;
+use quick_xml::escape::resolve_predefined_entity;
 use quick_xml::{Reader, events::Event};
 
-use crate::{Group, IsbnError, IsbnObject, IsbnRef};
+use crate::source::pack_digits;
+use crate::{
+    Audit, Isbn, IsbnBlock, IsbnError, IsbnParts, IsbnRef, RangeGroup, RangeLookup, RangeSource,
+    Rule,
+};
 
 struct Segment {
+    prefix: String,
     name: String,
-    // (start, stop, ?length).
-    ranges: Vec<((u32, u32), Option<NonZeroUsize>)>,
+    rules: Vec<Rule>,
 }
 
 pub struct IsbnRange {
@@ -26,11 +42,14 @@ pub struct IsbnRange {
 }
 
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum IsbnRangeError {
     NoIsbnRangeMessageTag,
     NoEanUccPrefixes,
+    #[deprecated(note = "other elements in EAN.UCCPrefixes are skipped")]
     NoEanUccPrefix,
     NoRegistrationGroups,
+    #[deprecated(note = "other elements in RegistrationGroups are skipped")]
     NoGroup,
     NoMessageDate,
     PrefixTooLong,
@@ -41,12 +60,107 @@ pub enum IsbnRangeError {
     NoDashInRange,
     Xml(quick_xml::Error),
     Encoding(quick_xml::encoding::EncodingError),
-    WrongXmlStart,
-    MissingXmlStart,
-    WrongXmlBody,
-    WrongXmlEnd,
-    MissingXmlEnd,
+    WrongXmlStart(XmlPosition),
+    #[deprecated(note = "missing elements are reported as `MissingElement`")]
+    MissingXmlStart(XmlPosition),
+    WrongXmlBody(XmlPosition),
+    #[deprecated(note = "mismatched end tags are reported as `Xml` errors")]
+    WrongXmlEnd(XmlPosition),
+    MissingXmlEnd(XmlPosition),
+    MissingElement(&'static str, XmlPosition),
     FileError(std::io::Error),
+    Inconsistent(Vec<RangeIssue>),
+}
+
+impl fmt::Display for IsbnRangeError {
+    #[allow(deprecated)]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IsbnRangeError::NoIsbnRangeMessageTag => {
+                write!(f, "The document has no ISBNRangeMessage element.")
+            }
+            IsbnRangeError::NoEanUccPrefixes => {
+                write!(f, "The document has no EAN.UCCPrefixes element.")
+            }
+            IsbnRangeError::NoEanUccPrefix => {
+                write!(
+                    f,
+                    "Encountered an element other than EAN.UCC in EAN.UCCPrefixes."
+                )
+            }
+            IsbnRangeError::NoRegistrationGroups => {
+                write!(f, "The document has no RegistrationGroups element.")
+            }
+            IsbnRangeError::NoGroup => {
+                write!(
+                    f,
+                    "Encountered an element other than Group in RegistrationGroups."
+                )
+            }
+            IsbnRangeError::NoMessageDate => write!(f, "The document has no MessageDate element."),
+            IsbnRangeError::PrefixTooLong => write!(f, "Encountered a prefix which is too long."),
+            IsbnRangeError::InvalidPrefixChar => {
+                write!(f, "Encountered an invalid character in a prefix.")
+            }
+            IsbnRangeError::BadLengthString => {
+                write!(f, "Encountered a rule length which is not a single digit.")
+            }
+            IsbnRangeError::LengthTooLarge => {
+                write!(f, "Encountered a rule length larger than 7.")
+            }
+            IsbnRangeError::BadRange => {
+                write!(f, "Encountered a rule range which is not a number.")
+            }
+            IsbnRangeError::NoDashInRange => {
+                write!(f, "Encountered a rule range without a dash.")
+            }
+            IsbnRangeError::Xml(_) => write!(f, "Failed to read the XML document."),
+            IsbnRangeError::Encoding(_) => write!(f, "Failed to decode the XML document."),
+            IsbnRangeError::WrongXmlStart(position) => {
+                write!(f, "Encountered an unexpected start tag at {}.", position)
+            }
+            IsbnRangeError::MissingXmlStart(position) => {
+                write!(f, "Expected a start tag at {}.", position)
+            }
+            IsbnRangeError::WrongXmlBody(position) => {
+                write!(f, "Expected text at {}.", position)
+            }
+            IsbnRangeError::WrongXmlEnd(position) => {
+                write!(f, "Encountered an unexpected end tag at {}.", position)
+            }
+            IsbnRangeError::MissingXmlEnd(position) => {
+                write!(f, "Expected an end tag at {}.", position)
+            }
+            IsbnRangeError::MissingElement(element, position) => write!(
+                f,
+                "Expected a {} element in the element opened at {}.",
+                element, position
+            ),
+            IsbnRangeError::FileError(_) => write!(f, "Failed to read the RangeMessage."),
+            IsbnRangeError::Inconsistent(issues) => {
+                write!(
+                    f,
+                    "Found {} inconsistencies in the ISBN ranges.",
+                    issues.len()
+                )?;
+                for issue in issues {
+                    write!(f, " {}", issue)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl error::Error for IsbnRangeError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            IsbnRangeError::Xml(e) => Some(e),
+            IsbnRangeError::Encoding(e) => Some(e),
+            IsbnRangeError::FileError(e) => Some(e),
+            _ => None,
+        }
+    }
 }
 
 impl From<quick_xml::Error> for IsbnRangeError {
@@ -67,229 +181,355 @@ impl From<std::io::Error> for IsbnRangeError {
     }
 }
 
-fn read_xml_tag<B: BufRead>(
-    reader: &mut Reader<B>,
-    buf: &mut Vec<u8>,
-    name: &[u8],
-) -> Result<String, IsbnRangeError> {
-    match reader.read_event_into(buf)? {
-        Event::Start(e) => {
-            if e.name().as_ref() != name {
-                return Err(IsbnRangeError::WrongXmlStart);
-            }
-        }
-        _ => return Err(IsbnRangeError::MissingXmlStart),
-    };
-    buf.clear();
-    let res = match reader.read_event_into(buf)? {
-        Event::Text(e) => e.decode()?.to_string(),
-        _ => return Err(IsbnRangeError::WrongXmlBody),
-    };
-    match reader.read_event_into(buf)? {
-        Event::End(e) => {
-            if e.name().as_ref() != name {
-                return Err(IsbnRangeError::WrongXmlEnd);
-            }
+/// A position in a RangeMessage document, just past the element where reading failed.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub struct XmlPosition {
+    line: usize,
+    column: usize,
+}
+
+impl XmlPosition {
+    /// The line, counting from 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column in bytes, counting from 1.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl fmt::Display for XmlPosition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// Keeps track of the line and column of the input consumed by the XML reader.
+struct LineReader<B> {
+    inner: B,
+    position: XmlPosition,
+}
+
+impl<B: BufRead> LineReader<B> {
+    fn new(inner: B) -> Self {
+        LineReader {
+            inner,
+            position: XmlPosition { line: 1, column: 1 },
         }
-        _ => return Err(IsbnRangeError::MissingXmlEnd),
-    };
-    buf.clear();
-    Ok(res)
+    }
 }
 
-fn read_xml_start<B: BufRead>(
-    reader: &mut Reader<B>,
-    buf: &mut Vec<u8>,
-    name: &[u8],
-) -> Result<bool, IsbnRangeError> {
-    if let Event::Start(e) = reader.read_event_into(buf)? {
-        let res = e.name().as_ref() == name;
-        buf.clear();
-        Ok(res)
-    } else {
-        buf.clear();
-        Ok(false)
+impl<B: BufRead> Read for LineReader<B> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let amt = available.len().min(buf.len());
+        buf[..amt].copy_from_slice(&available[..amt]);
+        self.consume(amt);
+        Ok(amt)
     }
 }
 
-impl Segment {
-    fn from_reader<B: BufRead>(
-        reader: &mut Reader<B>,
-        buf: &mut Vec<u8>,
-    ) -> Result<Self, IsbnRangeError> {
-        let name = read_xml_tag(reader, buf, b"Agency")?;
-        let mut ranges = Vec::new();
-
-        match reader.read_event_into(buf)? {
-            Event::Start(e) => {
-                if e.name().as_ref() != b"Rules" {
-                    return Err(IsbnRangeError::WrongXmlStart);
+impl<B: BufRead> BufRead for LineReader<B> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // The consumed bytes are still buffered, so this does not read from the inner reader.
+        if let Ok(buf) = self.inner.fill_buf() {
+            for &byte in &buf[..amt.min(buf.len())] {
+                if byte == b'\n' {
+                    self.position.line += 1;
+                    self.position.column = 1;
+                } else {
+                    self.position.column += 1;
                 }
             }
-            _ => return Err(IsbnRangeError::MissingXmlStart),
-        };
-        buf.clear();
+        }
+        self.inner.consume(amt);
+    }
+}
 
+type XmlReader<B> = Reader<LineReader<B>>;
+
+fn position<B>(reader: &XmlReader<B>) -> XmlPosition {
+    reader.get_ref().position
+}
+
+/// An element, text or the end of the document, as read by an [`XmlCursor`].
+enum Node {
+    Start(Vec<u8>),
+    End,
+    Text(String),
+    Eof,
+}
+
+/// Reads the elements and text of a RangeMessage, skipping comments, processing instructions
+/// and declarations. Empty elements are read as a start tag followed by an end tag.
+struct XmlCursor<B> {
+    reader: XmlReader<B>,
+    buf: Vec<u8>,
+    empty_end: bool,
+}
+
+impl<B: BufRead> XmlCursor<B> {
+    fn new(reader: B) -> Self {
+        XmlCursor {
+            reader: Reader::from_reader(LineReader::new(reader)),
+            buf: Vec::new(),
+            empty_end: false,
+        }
+    }
+
+    fn position(&self) -> XmlPosition {
+        position(&self.reader)
+    }
+
+    fn next(&mut self) -> Result<Node, IsbnRangeError> {
+        if mem::take(&mut self.empty_end) {
+            return Ok(Node::End);
+        }
         loop {
-            match reader.read_event_into(buf)? {
-                Event::Start(e) => {
-                    if e.name().as_ref() != b"Rule" {
-                        return Err(IsbnRangeError::WrongXmlStart);
-                    }
+            self.buf.clear();
+            let node = match self.reader.read_event_into(&mut self.buf)? {
+                Event::Start(e) => Node::Start(e.name().as_ref().to_vec()),
+                Event::Empty(e) => {
+                    self.empty_end = true;
+                    Node::Start(e.name().as_ref().to_vec())
                 }
-                Event::End(e) => {
-                    if e.name().as_ref() == b"Rules" {
-                        break;
-                    }
-                }
-                _ => return Err(IsbnRangeError::MissingXmlStart),
-            };
-            buf.clear();
-
-            let range = read_xml_tag(reader, buf, b"Range")?;
-            let length = read_xml_tag(reader, buf, b"Length")?;
-
-            ranges.push((
-                {
-                    let mid = range.find('-').ok_or(IsbnRangeError::NoDashInRange)?;
-                    let (a, b) = range.split_at(mid);
-                    (
-                        u32::from_str(a).map_err(|_| IsbnRangeError::BadRange)?,
-                        u32::from_str(b.split_at(1).1).map_err(|_| IsbnRangeError::BadRange)?,
-                    )
-                },
-                {
-                    if length.len() != 1 {
-                        return Err(IsbnRangeError::BadLengthString);
+                Event::End(_) => Node::End,
+                Event::Text(e) => Node::Text(e.xml_content()?.into_owned()),
+                Event::CData(e) => Node::Text(e.xml_content()?.into_owned()),
+                // Entities such as `&amp;` are read separately from the text around them.
+                Event::GeneralRef(e) => match e.resolve_char_ref()? {
+                    Some(c) => Node::Text(c.to_string()),
+                    None => {
+                        let text = resolve_predefined_entity(&e.decode()?)
+                            .ok_or_else(|| IsbnRangeError::WrongXmlBody(position(&self.reader)))?;
+                        Node::Text(text.to_string())
                     }
-                    let length = length
-                        .parse::<usize>()
-                        .map_err(|_| IsbnRangeError::BadLengthString)?;
-                    if length > 7 {
-                        return Err(IsbnRangeError::LengthTooLarge);
-                    }
-                    NonZeroUsize::new(length)
                 },
-            ));
-
-            match reader.read_event_into(buf)? {
-                Event::End(e) => {
-                    if e.name().as_ref() != b"Rule" {
-                        return Err(IsbnRangeError::WrongXmlEnd);
-                    }
-                }
-                _ => return Err(IsbnRangeError::MissingXmlEnd),
+                Event::Eof => Node::Eof,
+                Event::Comment(_) | Event::Decl(_) | Event::PI(_) | Event::DocType(_) => continue,
             };
-            buf.clear();
+            return Ok(node);
         }
+    }
 
-        match reader.read_event_into(buf)? {
-            Event::End(e) => match e.name().as_ref() {
-                b"EAN.UCC" | b"Group" => {}
-                _ => return Err(IsbnRangeError::WrongXmlEnd),
-            },
-            _ => return Err(IsbnRangeError::MissingXmlEnd),
-        };
-        buf.clear();
-
-        Ok(Segment { name, ranges })
+    /// Skips the rest of the element whose start tag was just read.
+    fn skip_element(&mut self) -> Result<(), IsbnRangeError> {
+        let mut depth = 1;
+        while depth > 0 {
+            match self.next()? {
+                Node::Start(_) => depth += 1,
+                Node::End => depth -= 1,
+                Node::Text(_) => {}
+                Node::Eof => return Err(IsbnRangeError::MissingXmlEnd(self.position())),
+            }
+        }
+        Ok(())
     }
 
-    fn group(&self, segment: u32) -> Result<Group<'_>, IsbnError> {
-        for ((start, stop), length) in &self.ranges {
-            if segment >= *start && segment < *stop {
-                let segment_length = usize::from(length.ok_or(IsbnError::UndefinedRange)?);
-                return Ok(Group {
-                    name: &self.name,
-                    segment_length,
-                });
+    /// Reads the trimmed text of the element whose start tag was just read.
+    fn read_text(&mut self) -> Result<String, IsbnRangeError> {
+        let mut text = String::new();
+        loop {
+            match self.next()? {
+                Node::Text(t) => text.push_str(&t),
+                Node::End => return Ok(text.trim().to_string()),
+                Node::Start(_) => return Err(IsbnRangeError::WrongXmlStart(self.position())),
+                Node::Eof => return Err(IsbnRangeError::MissingXmlEnd(self.position())),
             }
         }
-        Err(IsbnError::InvalidGroup)
     }
-}
 
-impl IsbnRange {
-    fn read_ean_ucc_group<B: BufRead>(
-        reader: &mut Reader<B>,
-        buf: &mut Vec<u8>,
-    ) -> Result<IndexMap<u16, Segment, RandomState>, IsbnRangeError> {
-        buf.clear();
-        let mut res = IndexMap::with_hasher(RandomState::new());
+    /// Reads the children of the element whose start tag was just read, in any order. `child`
+    /// is called with the name of each child, and reads the child if it returns `true`.
+    /// Other children are skipped.
+    fn read_children<F>(&mut self, mut child: F) -> Result<(), IsbnRangeError>
+    where
+        F: FnMut(&mut Self, &[u8]) -> Result<bool, IsbnRangeError>,
+    {
         loop {
-            match reader.read_event_into(buf)? {
-                Event::Start(e) => {
-                    if e.name().as_ref() != b"EAN.UCC" {
-                        return Err(IsbnRangeError::NoEanUccPrefix);
+            match self.next()? {
+                Node::Start(name) => {
+                    if !child(self, &name)? {
+                        self.skip_element()?;
                     }
                 }
-                Event::End(e) if e.name().as_ref() == b"EAN.UCCPrefixes" => {
-                    return Ok(res);
-                }
-                _ => return Err(IsbnRangeError::WrongXmlEnd),
-            };
-            buf.clear();
+                Node::End => return Ok(()),
+                Node::Text(_) => {}
+                Node::Eof => return Err(IsbnRangeError::MissingXmlEnd(self.position())),
+            }
+        }
+    }
 
-            let mut prefix_val = 0u16;
-            for (i, char) in read_xml_tag(reader, buf, b"Prefix")?.chars().enumerate() {
-                if i == 3 {
-                    return Err(IsbnRangeError::PrefixTooLong);
-                }
+    /// Reads the EAN.UCC prefixes or registration groups, given the name of each group.
+    fn read_groups<K: Hash + Eq>(
+        &mut self,
+        name: &[u8],
+        key: impl Fn(&str) -> Result<K, IsbnRangeError>,
+    ) -> Result<IndexMap<K, Segment, RandomState>, IsbnRangeError> {
+        let mut res = IndexMap::with_hasher(RandomState::new());
+        self.read_children(|cursor, element| {
+            if element != name {
+                return Ok(false);
+            }
+            let segment = Segment::from_cursor(cursor)?;
+            res.insert(key(&segment.prefix)?, segment);
+            Ok(true)
+        })?;
+        Ok(res)
+    }
+}
+
+/// Parses an EAN.UCC prefix such as `978` into its nibble-encoded key.
+fn parse_ean_ucc_prefix(prefix: &str) -> Result<u16, IsbnRangeError> {
+    let mut prefix_val = 0u16;
+    for (i, char) in prefix.chars().enumerate() {
+        if i == 3 {
+            return Err(IsbnRangeError::PrefixTooLong);
+        }
+        prefix_val =
+            (prefix_val << 4) | char.to_digit(10).ok_or(IsbnRangeError::InvalidPrefixChar)? as u16;
+    }
+    Ok(prefix_val)
+}
+
+/// Parses a registration group prefix such as `978-89` into its nibble-encoded key.
+fn parse_group_prefix(prefix: &str) -> Result<(u16, u32), IsbnRangeError> {
+    let mut prefix_val = 0u16;
+    let mut registration_group_element = 0u32;
+    for (i, char) in prefix.chars().enumerate() {
+        match i {
+            0..=2 => {
                 prefix_val = (prefix_val << 4)
                     | char.to_digit(10).ok_or(IsbnRangeError::InvalidPrefixChar)? as u16;
             }
+            3 => {
+                if char != '-' {
+                    return Err(IsbnRangeError::PrefixTooLong);
+                }
+            }
+            _ => {
+                registration_group_element = (registration_group_element << 4)
+                    | char.to_digit(10).ok_or(IsbnRangeError::InvalidPrefixChar)?;
+            }
+        }
+    }
+    Ok((prefix_val, registration_group_element))
+}
+
+/// Parses a rule from its range, such as `0000000-1999999`, and the length of the next element.
+fn parse_rule(range: &str, length: &str) -> Result<Rule, IsbnRangeError> {
+    let (start, stop) = {
+        let mid = range.find('-').ok_or(IsbnRangeError::NoDashInRange)?;
+        let (a, b) = range.split_at(mid);
+        (
+            u32::from_str(a).map_err(|_| IsbnRangeError::BadRange)?,
+            u32::from_str(b.split_at(1).1).map_err(|_| IsbnRangeError::BadRange)?,
+        )
+    };
+    if length.len() != 1 {
+        return Err(IsbnRangeError::BadLengthString);
+    }
+    let length = length
+        .parse::<usize>()
+        .map_err(|_| IsbnRangeError::BadLengthString)?;
+    if length > 7 {
+        return Err(IsbnRangeError::LengthTooLarge);
+    }
+    Ok(Rule {
+        start,
+        stop,
+        length,
+    })
+}
+
+/// Returns the value of a child element, or an error naming the missing element. `start` is
+/// the position just past the start tag of its parent.
+fn required<T>(
+    value: Option<T>,
+    element: &'static str,
+    start: XmlPosition,
+) -> Result<T, IsbnRangeError> {
+    value.ok_or(IsbnRangeError::MissingElement(element, start))
+}
+
+impl Segment {
+    fn from_cursor<B: BufRead>(cursor: &mut XmlCursor<B>) -> Result<Self, IsbnRangeError> {
+        let start = cursor.position();
+        let mut prefix = None;
+        let mut name = None;
+        let mut rules = None;
+        cursor.read_children(|cursor, element| {
+            match element {
+                b"Prefix" => prefix = Some(cursor.read_text()?),
+                b"Agency" => name = Some(cursor.read_text()?),
+                b"Rules" => rules = Some(Self::read_rules(cursor)?),
+                _ => return Ok(false),
+            }
+            Ok(true)
+        })?;
 
-            res.insert(prefix_val, Segment::from_reader(reader, buf)?);
+        let segment = Segment {
+            prefix: required(prefix, "Prefix", start)?,
+            name: required(name, "Agency", start)?,
+            rules: required(rules, "Rules", start)?,
+        };
+        // The prefix may follow the rules, so invalid ranges are only reported once both are read.
+        let issues: Vec<RangeIssue> = segment
+            .rules
+            .iter()
+            .filter(|rule| Rule::new(rule.start, rule.stop, rule.length).is_none())
+            .map(|&rule| RangeIssue::InvalidRule {
+                prefix: segment.prefix.clone(),
+                rule,
+            })
+            .collect();
+        if !issues.is_empty() {
+            return Err(IsbnRangeError::Inconsistent(issues));
         }
+        Ok(segment)
     }
 
-    fn read_registration_group<B: BufRead>(
-        reader: &mut Reader<B>,
-        buf: &mut Vec<u8>,
-    ) -> Result<IndexMap<(u16, u32), Segment, RandomState>, IsbnRangeError> {
-        buf.clear();
-        let mut res = IndexMap::with_hasher(RandomState::new());
-        loop {
-            match reader.read_event_into(buf)? {
-                Event::Start(e) => {
-                    if e.name().as_ref() != b"Group" {
-                        return Err(IsbnRangeError::NoGroup);
-                    }
-                }
-                Event::End(e) if e.name().as_ref() == b"RegistrationGroups" => {
-                    return Ok(res);
-                }
-                _ => return Err(IsbnRangeError::WrongXmlEnd),
-            };
-            buf.clear();
-
-            let mut prefix_val = 0u16;
-            let mut registration_group_element = 0u32;
-            for (i, char) in read_xml_tag(reader, buf, b"Prefix")?.chars().enumerate() {
-                match i {
-                    0..=2 => {
-                        prefix_val = (prefix_val << 4)
-                            | char.to_digit(10).ok_or(IsbnRangeError::InvalidPrefixChar)? as u16;
-                    }
-                    3 => {
-                        if char != '-' {
-                            return Err(IsbnRangeError::PrefixTooLong);
-                        }
-                    }
-                    _ => {
-                        registration_group_element = (registration_group_element << 4)
-                            | char.to_digit(10).ok_or(IsbnRangeError::InvalidPrefixChar)?;
-                    }
-                }
+    fn read_rules<B: BufRead>(cursor: &mut XmlCursor<B>) -> Result<Vec<Rule>, IsbnRangeError> {
+        let mut rules = Vec::new();
+        cursor.read_children(|cursor, element| {
+            if element != b"Rule" {
+                return Ok(false);
             }
+            let start = cursor.position();
+            let mut range = None;
+            let mut length = None;
+            cursor.read_children(|cursor, element| {
+                match element {
+                    b"Range" => range = Some(cursor.read_text()?),
+                    b"Length" => length = Some(cursor.read_text()?),
+                    _ => return Ok(false),
+                }
+                Ok(true)
+            })?;
+            let range = required(range, "Range", start)?;
+            let length = required(length, "Length", start)?;
+            rules.push(parse_rule(&range, &length)?);
+            Ok(true)
+        })?;
+        Ok(rules)
+    }
 
-            res.insert(
-                (prefix_val, registration_group_element),
-                Segment::from_reader(reader, buf)?,
-            );
+    fn range_group(&self) -> RangeGroup<'_> {
+        RangeGroup {
+            prefix: &self.prefix,
+            agency: &self.name,
+            rules: &self.rules,
         }
     }
+}
 
+impl IsbnRange {
     /// Reads the ISB ranges from the given reader. Should be used if the ISBN ranges need to be
     /// current. Up-to-date ISBN ranges can be downloaded from the following
     /// sources:
@@ -308,6 +548,9 @@ impl IsbnRange {
     /// ```
     /// # Errors
     /// If the RangeMessage is in an unexpected format or does not exist, an error will be returned.
+    /// A rule which starts after it stops, or stops after `9999999`, is reported as
+    /// [`IsbnRangeError::Inconsistent`]. Other inconsistencies are only found by
+    /// [`IsbnRange::validate`].
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, IsbnRangeError> {
         let reader = BufReader::new(File::open(path)?);
         Self::from_reader(reader)
@@ -319,6 +562,9 @@ impl IsbnRange {
     /// * https://www.isbn-international.org/export_rangemessage.xml
     /// * https://github.com/limeburst/isbn-ranges/blob/main/RangeMessage.xml
     ///
+    /// The elements of the RangeMessage may appear in any order. Comments, processing
+    /// instructions and elements which are not part of the ranges are skipped.
+    ///
     /// ```
     /// use isbn::{Isbn, Isbn10, Isbn13, IsbnRange};
     /// use std::io::BufReader;
@@ -335,61 +581,44 @@ impl IsbnRange {
     /// ```
     /// # Errors
     /// If the RangeMessage is in an unexpected format or does not exist, an error will be returned.
+    /// A rule which starts after it stops, or stops after `9999999`, is reported as
+    /// [`IsbnRangeError::Inconsistent`]. Other inconsistencies are only found by
+    /// [`IsbnRange::validate`].
     pub fn from_reader<B: BufRead>(reader: B) -> Result<Self, IsbnRangeError> {
-        let mut reader = Reader::from_reader(reader);
-        reader.config_mut().trim_text(true);
-        let mut buf = Vec::new();
+        let mut cursor = XmlCursor::new(reader);
         loop {
-            if read_xml_start(&mut reader, &mut buf, b"ISBNRangeMessage")? {
-                break;
+            match cursor.next()? {
+                Node::Start(name) if name == b"ISBNRangeMessage" => break,
+                Node::Start(_) => cursor.skip_element()?,
+                Node::End | Node::Text(_) => {}
+                Node::Eof => return Err(IsbnRangeError::NoIsbnRangeMessageTag),
             }
         }
 
-        let m_source = b"MessageSource";
-        let m_serial_number = b"MessageSerialNumber";
-        let m_date = b"MessageDate";
-        let mut fields = vec![m_date.as_ref(), m_serial_number.as_ref(), m_source.as_ref()];
-        let mut vals = vec![];
-        while !fields.is_empty() {
-            let mut buf1 = Vec::new();
-            let mut buf2 = Vec::new();
-            let e1 = reader.read_event_into(&mut buf)?;
-            let e2 = reader.read_event_into(&mut buf1)?;
-            let e3 = reader.read_event_into(&mut buf2)?;
-            match (e1, e2, e3) {
-                (Event::Start(es), Event::Text(et), Event::End(ee)) => {
-                    while let Some(name) = fields.pop() {
-                        if es.name().as_ref() == name {
-                            vals.push(Some(et.decode()?.to_string()));
-                            if ee.name().as_ref() != name {
-                                return Err(IsbnRangeError::WrongXmlEnd);
-                            }
-                            break;
-                        } else {
-                            vals.push(None);
-                        }
-                    }
+        let mut source = None;
+        let mut serial_number = None;
+        let mut date = None;
+        let mut ean_ucc_group = None;
+        let mut registration_group = None;
+        cursor.read_children(|cursor, element| {
+            match element {
+                b"MessageSource" => source = Some(cursor.read_text()?),
+                b"MessageSerialNumber" => serial_number = Some(cursor.read_text()?),
+                b"MessageDate" => date = Some(cursor.read_text()?),
+                b"EAN.UCCPrefixes" => {
+                    ean_ucc_group = Some(cursor.read_groups(b"EAN.UCC", parse_ean_ucc_prefix)?)
                 }
-                _ => return Err(IsbnRangeError::MissingXmlStart),
+                b"RegistrationGroups" => {
+                    registration_group = Some(cursor.read_groups(b"Group", parse_group_prefix)?)
+                }
+                _ => return Ok(false),
             }
-            buf.clear();
-        }
+            Ok(true)
+        })?;
 
-        let date = vals.pop().flatten().ok_or(IsbnRangeError::NoMessageDate)?;
-        let serial_number = vals.pop().expect("This error should not be possible");
-        let source = vals.pop().expect("This error should not be possible");
-
-        if !read_xml_start(&mut reader, &mut buf, b"EAN.UCCPrefixes")? {
-            return Err(IsbnRangeError::NoEanUccPrefixes);
-        }
-
-        let ean_ucc_group = Self::read_ean_ucc_group(&mut reader, &mut buf)?;
-
-        if !read_xml_start(&mut reader, &mut buf, b"RegistrationGroups")? {
-            return Err(IsbnRangeError::NoRegistrationGroups);
-        }
-
-        let registration_group = Self::read_registration_group(&mut reader, &mut buf)?;
+        let date = date.ok_or(IsbnRangeError::NoMessageDate)?;
+        let ean_ucc_group = ean_ucc_group.ok_or(IsbnRangeError::NoEanUccPrefixes)?;
+        let registration_group = registration_group.ok_or(IsbnRangeError::NoRegistrationGroups)?;
 
         Ok(IsbnRange {
             source,
@@ -426,35 +655,89 @@ impl IsbnRange {
         &self,
         isbn: I,
     ) -> Result<ArrayString<17>, IsbnError> {
-        match isbn.into() {
-            IsbnRef::_10(isbn) => self.hyphenate_isbn(isbn),
-            IsbnRef::_13(isbn) => self.hyphenate_isbn(isbn),
-        }
+        RangeSource::hyphenate(self, isbn.into())
+    }
+
+    /// Look up the groups and rules of `self` matching the ISBN, as with
+    /// [`Isbn::lookup_range`].
+    ///
+    /// ```
+    /// use isbn::{Isbn10, IsbnRange};
+    ///
+    /// let isbn_ranges = IsbnRange::from_path("isbn-ranges/RangeMessage.xml").unwrap();
+    /// let isbn_10 = Isbn10::new([8, 9, 6, 6, 2, 6, 1, 2, 6, 4]).unwrap();
+    /// let lookup = isbn_ranges.lookup_range(&isbn_10).unwrap();
+    ///
+    /// assert_eq!(lookup.registration_group().unwrap().agency(), "Korea, Republic");
+    /// assert_eq!(lookup.registrant_rule().unwrap().length(), 4);
+    /// ```
+    /// # Errors
+    /// If the EAN.UCC prefix of the ISBN or its rule is not found, or the rule gives the
+    /// registration group more than five digits, an error will be returned.
+    pub fn lookup_range<'a, I: Into<IsbnRef<'a>>>(
+        &self,
+        isbn: I,
+    ) -> Result<RangeLookup<'_>, IsbnError> {
+        RangeSource::lookup_range(self, isbn.into())
     }
 
-    fn hyphenate_isbn<I: IsbnObject>(&self, isbn: &I) -> Result<ArrayString<17>, IsbnError> {
-        let segment = self
-            .ean_ucc_group
-            .get(&isbn.prefix_element())
-            .ok_or(IsbnError::InvalidGroup)?;
-        let registration_group_segment_length = segment.group(isbn.segment(0))?.segment_length;
-        let segment = self
-            .registration_group
-            .get(&(
-                isbn.prefix_element(),
-                isbn.group_prefix(registration_group_segment_length),
-            ))
-            .ok_or(IsbnError::InvalidGroup)?;
-        let registrant_segment_length = segment
-            .group(isbn.segment(registration_group_segment_length))?
-            .segment_length;
+    /// Split an ISBN into its parts.
+    ///
+    /// ```
+    /// use isbn::{Isbn10, Isbn13, IsbnRange};
+    ///
+    /// let isbn_ranges = IsbnRange::from_path("isbn-ranges/RangeMessage.xml").unwrap();
+    ///
+    /// let isbn_10 = Isbn10::new([8, 9, 6, 6, 2, 6, 1, 2, 6, 4]).unwrap();
+    /// let isbn_13 = Isbn13::new([9, 7, 8, 1, 4, 9, 2, 0, 6, 7, 6, 6, 5]).unwrap();
+    ///
+    /// assert_eq!(isbn_ranges.parts(&isbn_10).unwrap().registrant(), "6626");
+    /// assert_eq!(isbn_ranges.parts(&isbn_13).unwrap().agency(), "English language");
+    /// ```
+    /// # Errors
+    /// If the ISBN is not valid, as determined by `self`, an error will be
+    /// returned.
+    pub fn parts<'a, I: Into<IsbnRef<'a>>>(&self, isbn: I) -> Result<IsbnParts<'_>, IsbnError> {
+        RangeSource::parts(self, isbn.into())
+    }
 
-        let hyphen_at = [
-            registration_group_segment_length,
-            registration_group_segment_length + registrant_segment_length,
-        ];
+    /// Parse an ISBN10 or ISBN13, requiring any hyphens to separate the elements given by
+    /// `self`. Spaces are treated as hyphens, and strings without any are accepted.
+    ///
+    /// ```
+    /// use isbn::{IsbnError, IsbnRange};
+    ///
+    /// let isbn_ranges = IsbnRange::from_path("isbn-ranges/RangeMessage.xml").unwrap();
+    ///
+    /// assert!(isbn_ranges.parse_hyphenated("978-3-16-148410-0").is_ok());
+    /// assert_eq!(
+    ///     isbn_ranges.parse_hyphenated("97-83161-48410-0"),
+    ///     Err(IsbnError::InvalidHyphenation("978-3-16-148410-0".try_into().unwrap()))
+    /// );
+    /// ```
+    /// # Errors
+    /// If the string is not an ISBN, its range is not defined by `self`, or its hyphens are
+    /// misplaced, an error will be returned.
+    pub fn parse_hyphenated(&self, s: &str) -> Result<Isbn, IsbnError> {
+        RangeSource::parse_hyphenated(self, s)
+    }
 
-        Ok(isbn.hyphenate_with(hyphen_at))
+    /// Look up the block of ISBNs available to a registrant, given its hyphenated prefix
+    /// such as `978-1-4920`.
+    ///
+    /// ```
+    /// use isbn::IsbnRange;
+    ///
+    /// let isbn_ranges = IsbnRange::from_path("isbn-ranges/RangeMessage.xml").unwrap();
+    ///
+    /// assert_eq!(isbn_ranges.block("978-1-4920").unwrap().size(), 10_000);
+    /// assert!(isbn_ranges.block("978-1-49").is_err());
+    /// ```
+    /// # Errors
+    /// If the prefix is malformed, or its registration group or registrant do not line up with
+    /// the ranges in `self`, an error will be returned.
+    pub fn block(&self, prefix: &str) -> Result<IsbnBlock, IsbnError> {
+        RangeSource::block(self, prefix)
     }
 
     /// Retrieve the name of the registration group.
@@ -478,28 +761,74 @@ impl IsbnRange {
         &self,
         isbn: I,
     ) -> Result<&str, IsbnError> {
-        match isbn.into() {
-            IsbnRef::_10(isbn) => self.get_registration_group_isbn(isbn),
-            IsbnRef::_13(isbn) => self.get_registration_group_isbn(isbn),
-        }
+        RangeSource::get_registration_group(self, isbn.into())
+    }
+
+    /// Compare stored, hyphenated ISBNs with `self`, such as to find the ISBNs hyphenated
+    /// differently after the ranges are updated.
+    ///
+    /// ```
+    /// use isbn::{AuditStatus, IsbnRange};
+    ///
+    /// let isbn_ranges = IsbnRange::from_path("isbn-ranges/RangeMessage.xml").unwrap();
+    /// let stored = ["89-6626-126-4", "89-662-6126-4"];
+    /// let mut audit = isbn_ranges.audit(stored);
+    ///
+    /// assert_eq!(audit.next(), Some(("89-6626-126-4", AuditStatus::Unchanged)));
+    /// assert_eq!(
+    ///     audit.next(),
+    ///     Some((
+    ///         "89-662-6126-4",
+    ///         AuditStatus::Rehyphenated("89-6626-126-4".try_into().unwrap())
+    ///     ))
+    /// );
+    /// ```
+    pub fn audit<I>(&self, hyphenated: I) -> Audit<'_, I::IntoIter, IsbnRange>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        RangeSource::audit(self, hyphenated)
+    }
+
+    /// The EAN.UCC prefixes of `self`, in the order of the RangeMessage. This has the same
+    /// shape as [`Isbn::ean_ucc_groups`], so the same code can list either.
+    ///
+    /// ```
+    /// use isbn::{Isbn, IsbnRange, RangeGroup};
+    ///
+    /// fn prefixes<'a>(groups: impl Iterator<Item = RangeGroup<'a>>) -> Vec<&'a str> {
+    ///     groups.map(|g| g.prefix()).collect()
+    /// }
+    ///
+    /// let isbn_ranges = IsbnRange::from_path("isbn-ranges/RangeMessage.xml").unwrap();
+    ///
+    /// assert_eq!(prefixes(isbn_ranges.ean_ucc_groups()), ["978", "979"]);
+    /// assert_eq!(prefixes(Isbn::ean_ucc_groups()), ["978", "979"]);
+    /// ```
+    pub fn ean_ucc_groups(
+        &self,
+    ) -> impl ExactSizeIterator<Item = RangeGroup<'_>> + DoubleEndedIterator {
+        self.ean_ucc_group.values().map(Segment::range_group)
     }
 
-    fn get_registration_group_isbn<I: IsbnObject>(&self, isbn: &I) -> Result<&str, IsbnError> {
-        let segment = self
-            .ean_ucc_group
-            .get(&isbn.prefix_element())
-            .ok_or(IsbnError::InvalidGroup)?;
-        let registration_group_segment_length = segment.group(isbn.segment(0))?.segment_length;
-        let segment = self
-            .registration_group
-            .get(&(
-                isbn.prefix_element(),
-                isbn.group_prefix(registration_group_segment_length),
-            ))
-            .ok_or(IsbnError::InvalidGroup)?;
-        Ok(segment
-            .group(isbn.segment(registration_group_segment_length))?
-            .name)
+    /// The registration groups of `self`, in the order of the RangeMessage.
+    ///
+    /// ```
+    /// use isbn::IsbnRange;
+    ///
+    /// let isbn_ranges = IsbnRange::from_path("isbn-ranges/RangeMessage.xml").unwrap();
+    ///
+    /// let korea = isbn_ranges
+    ///     .registration_groups()
+    ///     .find(|g| g.prefix() == "978-89")
+    ///     .unwrap();
+    /// assert_eq!(korea.agency(), "Korea, Republic");
+    /// ```
+    pub fn registration_groups(
+        &self,
+    ) -> impl ExactSizeIterator<Item = RangeGroup<'_>> + DoubleEndedIterator {
+        self.registration_group.values().map(Segment::range_group)
     }
 
     pub fn date(&self) -> &str {
@@ -521,10 +850,242 @@ impl IsbnRange {
     }
 }
 
+impl RangeSource for IsbnRange {
+    fn find_ean_ucc_group(&self, prefix: [u8; 3]) -> Option<RangeGroup<'_>> {
+        let prefix = pack_digits(&prefix)? as u16;
+        self.ean_ucc_group.get(&prefix).map(Segment::range_group)
+    }
+
+    fn find_registration_group(
+        &self,
+        prefix: [u8; 3],
+        registration_group: &[u8],
+    ) -> Option<RangeGroup<'_>> {
+        let key = (
+            pack_digits(&prefix)? as u16,
+            pack_digits(registration_group)?,
+        );
+        self.registration_group.get(&key).map(Segment::range_group)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::Isbn;
+    use crate::{AuditStatus, Isbn10, Isbn13};
+
+    /// An EAN.UCC prefix or registration group as `(prefix, agency, [(range, length)])`.
+    pub(super) type TestGroup<'a> = (&'a str, &'a str, &'a [(&'a str, usize)]);
+
+    /// Writes a RangeMessage with the given EAN.UCC prefixes and registration groups.
+    pub(super) fn range_message(ean_ucc: &[TestGroup], groups: &[TestGroup]) -> String {
+        let write_groups = |xml: &mut String, tag: &str, groups: &[TestGroup]| {
+            for (prefix, agency, rules) in groups {
+                xml.push_str(&format!(
+                    "<{tag}><Prefix>{prefix}</Prefix><Agency>{agency}</Agency><Rules>"
+                ));
+                for (range, length) in *rules {
+                    xml.push_str(&format!(
+                        "<Rule><Range>{range}</Range><Length>{length}</Length></Rule>"
+                    ));
+                }
+                xml.push_str(&format!("</Rules></{tag}>"));
+            }
+        };
+        let mut xml = String::from(
+            "<ISBNRangeMessage><MessageSource>International ISBN Agency</MessageSource>\
+             <MessageSerialNumber>1</MessageSerialNumber>\
+             <MessageDate>Sat, 22 Aug 2026 12:00:00 BST</MessageDate><EAN.UCCPrefixes>",
+        );
+        write_groups(&mut xml, "EAN.UCC", ean_ucc);
+        xml.push_str("</EAN.UCCPrefixes><RegistrationGroups>");
+        write_groups(&mut xml, "Group", groups);
+        xml.push_str("</RegistrationGroups></ISBNRangeMessage>");
+        xml
+    }
+
+    #[test]
+    fn test_xml_error_position() {
+        let xml = "<ISBNRangeMessage>
+  <MessageSource>International ISBN Agency</MessageSource>
+  <MessageSerialNumber>1</MessageSerialNumber>
+  <MessageDate>Sat, 22 Aug 2026 12:00:00 BST</MessageDate>
+  <EAN.UCCPrefixes>
+    <EAN.UCC>
+      <Prefix>978</Prefix>
+      <Agency>International ISBN Agency</Agency>
+      <Rule>";
+        match IsbnRange::from_reader(xml.as_bytes()) {
+            Err(e @ IsbnRangeError::MissingXmlEnd(position)) => {
+                assert_eq!((position.line(), position.column()), (9, 13));
+                assert_eq!(e.to_string(), "Expected an end tag at line 9, column 13.");
+            }
+            _ => panic!("expected MissingXmlEnd"),
+        }
+
+        let e = IsbnRange::from_path("isbn-ranges/NoSuchFile.xml")
+            .err()
+            .unwrap();
+        assert!(error::Error::source(&e).is_some());
+    }
+
+    #[test]
+    fn test_read_unordered_xml() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- Exported by hand -->
+<ISBNRangeMessage xmlns:x="urn:example">
+  <MessageDate>Sat, 22 Aug 2026 12:00:00 BST</MessageDate>
+  <x:Comment>Not part of <b>the</b> ranges</x:Comment>
+  <RegistrationGroups>
+    <Group>
+      <Agency>Côte d&apos;Ivoire</Agency>
+      <Rules>
+        <Rule><Length>3</Length><Range>0000000-9999999</Range></Rule>
+      </Rules>
+      <Prefix>979-10</Prefix>
+    </Group>
+    <!-- <Group><Prefix>978-0</Prefix></Group> -->
+    <Group>
+      <Prefix> 978-0 </Prefix>
+      <Agency>English &amp; Fran&#231;ais <![CDATA[<language>]]></Agency>
+      <Updated/>
+      <Rules>
+        <Rule>
+          <Range>0000000-1999999</Range>
+          <Length>2</Length>
+        </Rule>
+        <Note>The remaining segments are not defined.</Note>
+        <Rule><Range>2000000-9999999</Range><Length>0</Length></Rule>
+      </Rules>
+    </Group>
+  </RegistrationGroups>
+  <MessageSerialNumber>1</MessageSerialNumber>
+  <EAN.UCCPrefixes>
+    <EAN.UCC>
+      <Prefix>978</Prefix>
+      <Agency>International ISBN Agency</Agency>
+      <Rules><Rule><Range>0000000-9999999</Range><Length>1</Length></Rule></Rules>
+    </EAN.UCC>
+  </EAN.UCCPrefixes>
+</ISBNRangeMessage>"#;
+        let isbn_ranges = IsbnRange::from_reader(xml.as_bytes()).unwrap();
+        assert_eq!(isbn_ranges.source(), None);
+        assert_eq!(isbn_ranges.serial_number(), Some("1"));
+        assert_eq!(isbn_ranges.date(), "Sat, 22 Aug 2026 12:00:00 BST");
+        assert_eq!(
+            isbn_ranges
+                .registration_groups()
+                .map(|group| (group.prefix(), group.agency()))
+                .collect::<Vec<_>>(),
+            [
+                ("979-10", "Côte d'Ivoire"),
+                ("978-0", "English & Français <language>"),
+            ]
+        );
+
+        let expected = range_message(
+            &[(
+                "978",
+                "International ISBN Agency",
+                &[("0000000-9999999", 1)],
+            )],
+            &[
+                ("979-10", "Côte d'Ivoire", &[("0000000-9999999", 3)]),
+                (
+                    "978-0",
+                    "English &amp; Français &lt;language&gt;",
+                    &[("0000000-1999999", 2), ("2000000-9999999", 0)],
+                ),
+            ],
+        );
+        let expected = IsbnRange::from_reader(expected.as_bytes()).unwrap();
+        assert!(isbn_ranges.diff(&expected).is_empty());
+        assert!(
+            isbn_ranges
+                .registration_groups()
+                .eq(expected.registration_groups())
+        );
+    }
+
+    #[test]
+    fn test_read_incomplete_xml() {
+        let xml = range_message(
+            &[(
+                "978",
+                "International ISBN Agency",
+                &[("0000000-9999999", 1)],
+            )],
+            &[("978-0", "English language", &[("0000000-9999999", 2)])],
+        );
+        let read = |xml: &str| IsbnRange::from_reader(xml.as_bytes()).err();
+
+        assert!(matches!(
+            read(&xml.replace(
+                "<MessageDate>Sat, 22 Aug 2026 12:00:00 BST</MessageDate>",
+                ""
+            )),
+            Some(IsbnRangeError::NoMessageDate)
+        ));
+        assert!(matches!(
+            read(&xml.replace("<Length>2</Length>", "")),
+            Some(IsbnRangeError::MissingElement("Length", _))
+        ));
+        assert!(matches!(
+            read(&xml.replace("<Prefix>978-0</Prefix>", "")),
+            Some(IsbnRangeError::MissingElement("Prefix", _))
+        ));
+        assert!(matches!(
+            read(&xml.replace("<Agency>English language</Agency>", "")),
+            Some(IsbnRangeError::MissingElement("Agency", _))
+        ));
+        let no_rules = xml.replace("<Group>", "\n  <Group>").replace(
+            "<Rules><Rule><Range>0000000-9999999</Range><Length>2</Length></Rule></Rules>",
+            "",
+        );
+        match read(&no_rules) {
+            Some(e @ IsbnRangeError::MissingElement("Rules", position)) => {
+                assert_eq!((position.line(), position.column()), (2, 10));
+                assert_eq!(
+                    e.to_string(),
+                    "Expected a Rules element in the element opened at line 2, column 10."
+                );
+            }
+            e => panic!("expected MissingElement, found {:?}", e),
+        }
+        assert!(matches!(
+            read(&xml.replace("English language", "English &nbsp;")),
+            Some(IsbnRangeError::WrongXmlBody(_))
+        ));
+        assert!(matches!(
+            read(&xml.replace("English language", "English <i>language</i>")),
+            Some(IsbnRangeError::WrongXmlStart(_))
+        ));
+        match read(&xml.replace(
+            "<Range>0000000-9999999</Range><Length>2</Length>",
+            "<Range>9999999-9000000</Range><Length>2</Length>",
+        )) {
+            Some(IsbnRangeError::Inconsistent(issues)) => assert_eq!(
+                issues,
+                [RangeIssue::InvalidRule {
+                    prefix: "978-0".to_string(),
+                    rule: Rule {
+                        start: 9_999_999,
+                        stop: 9_000_000,
+                        length: 2
+                    },
+                }]
+            ),
+            e => panic!("expected Inconsistent, found {:?}", e),
+        }
+        assert!(matches!(
+            read(&xml.replace("0000000-9999999", "0000000-10000000")),
+            Some(IsbnRangeError::Inconsistent(_))
+        ));
+        assert!(matches!(
+            read("<!-- ISBNRangeMessage --><Other/>"),
+            Some(IsbnRangeError::NoIsbnRangeMessageTag)
+        ));
+    }
 
     #[test]
     fn test_isbn_range_opens() {
@@ -551,4 +1112,117 @@ mod test {
                 .is_ok()
         );
     }
+
+    #[test]
+    fn test_parts_match_hyphenation() {
+        let range = IsbnRange::from_path("./isbn-ranges/RangeMessage.xml").unwrap();
+        for s in ["0-9752298-0-X", "978-3-16-148410-0", "89-6626-126-4"] {
+            let isbn = Isbn::from_str(s).unwrap();
+            let parts = range.parts(&isbn).unwrap();
+            assert_eq!(parts.hyphenate(), range.hyphenate(&isbn).unwrap());
+            assert_eq!(parts.hyphenate(), isbn.parts().unwrap().hyphenate());
+        }
+    }
+
+    #[test]
+    fn test_lookup_range_matches_compiled() {
+        let range = IsbnRange::from_path("./isbn-ranges/RangeMessage.xml").unwrap();
+        for s in ["0-9752298-0-X", "978-3-16-148410-0", "979-0-000-00000-1"] {
+            let isbn = Isbn::from_str(s).unwrap();
+            assert_eq!(range.lookup_range(&isbn), isbn.lookup_range());
+        }
+    }
+
+    /// The ISBN13 following the digits of the prefix with the segment, truncated to fit.
+    fn isbn_at(prefix: &str, segment: u32) -> Isbn13 {
+        let digits = prefix
+            .bytes()
+            .filter(|&b| b != b'-')
+            .chain(format!("{:07}", segment).into_bytes());
+        let mut body = [0; 12];
+        for (digit, b) in body.iter_mut().zip(digits) {
+            *digit = b - b'0';
+        }
+        Isbn13::from_body(body).unwrap()
+    }
+
+    #[test]
+    fn test_rule_boundaries_match_compiled() {
+        let range = IsbnRange::from_path("./isbn-ranges/RangeMessage.xml").unwrap();
+        let mut checked = 0;
+        for group in range.ean_ucc_groups().chain(range.registration_groups()) {
+            for rule in group.rules() {
+                let segments = [
+                    rule.start().checked_sub(1),
+                    Some(rule.start()),
+                    Some(rule.stop()),
+                    Some(rule.stop() + 1).filter(|&s| s <= 9_999_999),
+                ];
+                for segment in segments.into_iter().flatten() {
+                    let isbn_13 = isbn_at(group.prefix(), segment);
+                    let isbn_10: Option<Isbn10> = isbn_13.try_into().ok();
+                    for isbn in [Some(Isbn::_13(isbn_13)), isbn_10.map(Isbn::_10)]
+                        .into_iter()
+                        .flatten()
+                    {
+                        assert_eq!(range.hyphenate(&isbn), isbn.hyphenate(), "{}", isbn);
+                        assert_eq!(range.parts(&isbn), isbn.parts(), "{}", isbn);
+                        assert_eq!(range.lookup_range(&isbn), isbn.lookup_range(), "{}", isbn);
+                        if let Ok(lookup) = range.lookup_range(&isbn) {
+                            assert_eq!(lookup.error(), range.hyphenate(&isbn).err(), "{}", isbn);
+                        }
+                        checked += 1;
+                    }
+                }
+
+                // Rules include both their start and stop.
+                let group_length = group.prefix().len().saturating_sub(4);
+                if group_length == 0 || group_length + rule.length() >= 9 {
+                    continue;
+                }
+                for segment in [rule.start(), rule.stop()] {
+                    let parts = range.parts(&isbn_at(group.prefix(), segment));
+                    if rule.is_reserved() {
+                        assert_eq!(parts, Err(IsbnError::UndefinedRange));
+                    } else {
+                        let parts = parts.unwrap();
+                        assert_eq!(parts.registrant().len(), rule.length(), "{}", parts);
+                    }
+                }
+            }
+        }
+        assert!(checked > 0);
+    }
+
+    #[test]
+    fn test_groups_match_compiled() {
+        let range = IsbnRange::from_path("./isbn-ranges/RangeMessage.xml").unwrap();
+        assert!(range.ean_ucc_groups().eq(Isbn::ean_ucc_groups()));
+        assert!(range.registration_groups().eq(Isbn::registration_groups()));
+    }
+
+    #[test]
+    fn test_audit() {
+        let xml = range_message(
+            &[(
+                "978",
+                "International ISBN Agency",
+                &[("0000000-1999999", 2), ("2000000-9999999", 0)],
+            )],
+            &[("978-14", "English language", &[("0000000-9999999", 3)])],
+        );
+        let range = IsbnRange::from_reader(xml.as_bytes()).unwrap();
+        let statuses: Vec<_> = range
+            .audit(["978-1-4920-6766-5", "978-3-16-148410-0", "978-10-4920-676"])
+            .map(|(_, status)| status)
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                AuditStatus::MovedGroup(ArrayString::from("978-14-920-6766-5").unwrap()),
+                AuditStatus::Undefined(IsbnError::UndefinedRange),
+                AuditStatus::Invalid(IsbnError::InvalidLength),
+            ]
+        );
+    }
 }
@@ -0,0 +1,208 @@
+use std::hash::{Hash, RandomState};
+
+use indexmap::IndexMap;
+
+use super::{IsbnRange, Segment};
+use crate::{RangeGroup, Rule};
+
+/// The differences between two versions of the ISBN ranges, returned by [`IsbnRange::diff`].
+///
+/// Both EAN.UCC prefixes and registration groups are compared.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct RangeDiff<'a> {
+    added: Vec<RangeGroup<'a>>,
+    removed: Vec<RangeGroup<'a>>,
+    renamed: Vec<(RangeGroup<'a>, RangeGroup<'a>)>,
+    changed_rules: Vec<RuleChange<'a>>,
+}
+
+impl<'a> RangeDiff<'a> {
+    /// The groups only in the new ranges.
+    pub fn added(&self) -> &[RangeGroup<'a>] {
+        &self.added
+    }
+
+    /// The groups only in the old ranges.
+    pub fn removed(&self) -> &[RangeGroup<'a>] {
+        &self.removed
+    }
+
+    /// The groups whose agency was renamed, as `(old, new)`.
+    pub fn renamed(&self) -> &[(RangeGroup<'a>, RangeGroup<'a>)] {
+        &self.renamed
+    }
+
+    /// The overlapping rules of groups in both ranges which give different lengths. ISBNs in
+    /// these ranges are hyphenated differently, or are no longer or newly defined.
+    pub fn changed_rules(&self) -> &[RuleChange<'a>] {
+        &self.changed_rules
+    }
+
+    /// Whether the ranges define the same groups, agencies and lengths.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.renamed.is_empty()
+            && self.changed_rules.is_empty()
+    }
+
+    fn compare<K: Hash + Eq>(
+        &mut self,
+        old: &'a IndexMap<K, Segment, RandomState>,
+        new: &'a IndexMap<K, Segment, RandomState>,
+    ) {
+        for (key, old_segment) in old {
+            let Some(new_segment) = new.get(key) else {
+                self.removed.push(old_segment.range_group());
+                continue;
+            };
+            if old_segment.name != new_segment.name {
+                self.renamed
+                    .push((old_segment.range_group(), new_segment.range_group()));
+            }
+            for old_rule in &old_segment.rules {
+                for new_rule in &new_segment.rules {
+                    if old_rule.length != new_rule.length
+                        && old_rule.start <= new_rule.stop
+                        && new_rule.start <= old_rule.stop
+                    {
+                        self.changed_rules.push(RuleChange {
+                            prefix: &new_segment.prefix,
+                            old: *old_rule,
+                            new: *new_rule,
+                        });
+                    }
+                }
+            }
+        }
+        for (key, new_segment) in new {
+            if !old.contains_key(key) {
+                self.added.push(new_segment.range_group());
+            }
+        }
+    }
+}
+
+/// A pair of overlapping rules of the same group which give different lengths.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub struct RuleChange<'a> {
+    prefix: &'a str,
+    old: Rule,
+    new: Rule,
+}
+
+impl<'a> RuleChange<'a> {
+    /// The prefix of the group, such as `978-89`.
+    pub fn prefix(&self) -> &'a str {
+        self.prefix
+    }
+
+    /// The rule in the old ranges.
+    pub fn old_rule(&self) -> Rule {
+        self.old
+    }
+
+    /// The rule in the new ranges.
+    pub fn new_rule(&self) -> Rule {
+        self.new
+    }
+
+    /// The first segment covered by both rules.
+    pub fn start(&self) -> u32 {
+        self.old.start.max(self.new.start)
+    }
+
+    /// The last segment covered by both rules, inclusive.
+    pub fn stop(&self) -> u32 {
+        self.old.stop.min(self.new.stop)
+    }
+}
+
+impl IsbnRange {
+    /// Compare `self` with a newer version of the ISBN ranges.
+    ///
+    /// ```
+    /// use isbn::IsbnRange;
+    ///
+    /// let isbn_ranges = IsbnRange::from_path("isbn-ranges/RangeMessage.xml").unwrap();
+    ///
+    /// assert!(isbn_ranges.diff(&isbn_ranges).is_empty());
+    /// ```
+    pub fn diff<'a>(&'a self, new: &'a IsbnRange) -> RangeDiff<'a> {
+        let mut diff = RangeDiff::default();
+        diff.compare(&self.ean_ucc_group, &new.ean_ucc_group);
+        diff.compare(&self.registration_group, &new.registration_group);
+        diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test::range_message;
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let ean_ucc = [(
+            "978",
+            "International ISBN Agency",
+            &[("0000000-9999999", 1)][..],
+        )];
+        let old = range_message(
+            &ean_ucc,
+            &[
+                (
+                    "978-0",
+                    "English language",
+                    &[("0000000-4999999", 2), ("5000000-9999999", 0)],
+                ),
+                ("978-1", "English language", &[("0000000-9999999", 3)]),
+                ("978-2", "French language", &[("0000000-9999999", 3)]),
+            ],
+        );
+        let new = range_message(
+            &ean_ucc,
+            &[
+                (
+                    "978-0",
+                    "English",
+                    &[("0000000-5999999", 2), ("6000000-9999999", 4)],
+                ),
+                ("978-1", "English language", &[("0000000-9999999", 3)]),
+                ("978-3", "German language", &[("0000000-9999999", 3)]),
+            ],
+        );
+        let old = IsbnRange::from_reader(old.as_bytes()).unwrap();
+        let new = IsbnRange::from_reader(new.as_bytes()).unwrap();
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.added().len(), 1);
+        assert_eq!(diff.added()[0].prefix(), "978-3");
+        assert_eq!(diff.removed().len(), 1);
+        assert_eq!(diff.removed()[0].prefix(), "978-2");
+        assert_eq!(diff.renamed().len(), 1);
+        assert_eq!(diff.renamed()[0].1.agency(), "English");
+
+        let changes: Vec<_> = diff
+            .changed_rules()
+            .iter()
+            .map(|c| {
+                (
+                    c.prefix(),
+                    c.start(),
+                    c.stop(),
+                    c.old_rule().length(),
+                    c.new_rule().length(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                ("978-0", 5000000, 5999999, 0, 2),
+                ("978-0", 6000000, 9999999, 0, 4)
+            ]
+        );
+        assert!(new.diff(&new).is_empty());
+    }
+}
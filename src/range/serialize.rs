@@ -0,0 +1,149 @@
+use std::hash::RandomState;
+
+use indexmap::IndexMap;
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+use super::{IsbnRange, IsbnRangeError, Segment, parse_ean_ucc_prefix, parse_group_prefix};
+use crate::Rule;
+
+/// The serialized form of [`IsbnRange`], mirroring the RangeMessage with each rule written as
+/// `[start, stop, length]`.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "IsbnRange")]
+struct IsbnRangeData<S> {
+    source: Option<S>,
+    serial_number: Option<S>,
+    date: S,
+    ean_ucc: Vec<SegmentData<S>>,
+    registration_groups: Vec<SegmentData<S>>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "Segment")]
+struct SegmentData<S> {
+    prefix: S,
+    agency: S,
+    rules: Vec<(u32, u32, usize)>,
+}
+
+impl<'a> From<&'a Segment> for SegmentData<&'a str> {
+    fn from(segment: &'a Segment) -> Self {
+        SegmentData {
+            prefix: &segment.prefix,
+            agency: &segment.name,
+            rules: segment
+                .rules
+                .iter()
+                .map(|rule| (rule.start, rule.stop, rule.length))
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<SegmentData<String>> for Segment {
+    type Error = IsbnRangeError;
+
+    fn try_from(data: SegmentData<String>) -> Result<Self, IsbnRangeError> {
+        let rules = data
+            .rules
+            .into_iter()
+            .map(|(start, stop, length)| {
+                if length > 7 {
+                    return Err(IsbnRangeError::LengthTooLarge);
+                }
+                Ok(Rule {
+                    start,
+                    stop,
+                    length,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Segment {
+            prefix: data.prefix,
+            name: data.agency,
+            rules,
+        })
+    }
+}
+
+impl Serialize for IsbnRange {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        IsbnRangeData {
+            source: self.source.as_deref(),
+            serial_number: self.serial_number.as_deref(),
+            date: self.date.as_str(),
+            ean_ucc: self.ean_ucc_group.values().map(SegmentData::from).collect(),
+            registration_groups: self
+                .registration_group
+                .values()
+                .map(SegmentData::from)
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for IsbnRange {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = IsbnRangeData::<String>::deserialize(deserializer)?;
+        let segment =
+            |data: SegmentData<String>| Segment::try_from(data).map_err(de::Error::custom);
+
+        let mut ean_ucc_group = IndexMap::with_hasher(RandomState::new());
+        for data in data.ean_ucc {
+            let prefix = parse_ean_ucc_prefix(&data.prefix).map_err(de::Error::custom)?;
+            ean_ucc_group.insert(prefix, segment(data)?);
+        }
+        let mut registration_group = IndexMap::with_hasher(RandomState::new());
+        for data in data.registration_groups {
+            let prefix = parse_group_prefix(&data.prefix).map_err(de::Error::custom)?;
+            registration_group.insert(prefix, segment(data)?);
+        }
+
+        Ok(IsbnRange {
+            source: data.source,
+            serial_number: data.serial_number,
+            date: data.date,
+            ean_ucc_group,
+            registration_group,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test::range_message;
+    use super::*;
+
+    #[test]
+    fn test_serialize() {
+        let xml = range_message(
+            &[(
+                "978",
+                "International ISBN Agency",
+                &[("0000000-9999999", 1)],
+            )],
+            &[(
+                "978-0",
+                "English language",
+                &[("0000000-1999999", 2), ("2000000-9999999", 0)],
+            )],
+        );
+        let isbn_ranges = IsbnRange::from_reader(xml.as_bytes()).unwrap();
+
+        let json = serde_json::to_string(&isbn_ranges).unwrap();
+        assert_eq!(
+            json,
+            r#"{"source":"International ISBN Agency","serial_number":"1","date":"Sat, 22 Aug 2026 12:00:00 BST","ean_ucc":[{"prefix":"978","agency":"International ISBN Agency","rules":[[0,9999999,1]]}],"registration_groups":[{"prefix":"978-0","agency":"English language","rules":[[0,1999999,2],[2000000,9999999,0]]}]}"#
+        );
+        let deserialized: IsbnRange = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.date(), isbn_ranges.date());
+        assert!(deserialized.diff(&isbn_ranges).is_empty());
+
+        assert!(serde_json::from_str::<IsbnRange>(&json.replace("978-0", "9780")).is_err());
+        assert!(
+            serde_json::from_str::<IsbnRange>(&json.replace("1999999,2", "1999999,8")).is_err()
+        );
+    }
+}
@@ -0,0 +1,278 @@
+use std::fs;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime};
+
+use super::{IsbnRange, IsbnRangeError};
+
+/// Shares the current ISBN ranges between threads, and replaces them when newer ranges are
+/// loaded.
+///
+/// Readers take a snapshot of the ranges with [`IsbnRangeStore::load`], which stays valid even
+/// if the ranges are replaced while it is in use. New ranges are read before they replace the
+/// current ranges, so readers never wait for a reload, and ranges which fail to load never
+/// replace the current ranges.
+///
+/// # Examples
+///
+/// ```
+/// use isbn::{Isbn, IsbnRangeStore};
+///
+/// let store = IsbnRangeStore::from_path("isbn-ranges/RangeMessage.xml").unwrap();
+/// let isbn: Isbn = "9781492067665".parse().unwrap();
+///
+/// let isbn_ranges = store.load();
+/// assert_eq!(isbn_ranges.hyphenate(&isbn).unwrap().as_str(), "978-1-4920-6766-5");
+///
+/// store.reload_from_path("isbn-ranges/RangeMessage.xml").unwrap();
+/// assert_eq!(store.date(), isbn_ranges.date());
+/// ```
+pub struct IsbnRangeStore {
+    current: RwLock<Arc<IsbnRange>>,
+}
+
+impl IsbnRangeStore {
+    /// Creates a store holding the given ranges.
+    pub fn new(isbn_ranges: IsbnRange) -> Self {
+        IsbnRangeStore {
+            current: RwLock::new(Arc::new(isbn_ranges)),
+        }
+    }
+
+    /// Creates a store holding the ranges read from the given path.
+    ///
+    /// # Errors
+    /// If the RangeMessage is in an unexpected format or does not exist, an error will be returned.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, IsbnRangeError> {
+        Ok(Self::new(IsbnRange::from_path(path)?))
+    }
+
+    /// The current ranges.
+    pub fn load(&self) -> Arc<IsbnRange> {
+        Arc::clone(&self.current.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Replaces the current ranges, returning the previous ranges.
+    pub fn store(&self, isbn_ranges: IsbnRange) -> Arc<IsbnRange> {
+        let mut current = self.current.write().unwrap_or_else(PoisonError::into_inner);
+        std::mem::replace(&mut *current, Arc::new(isbn_ranges))
+    }
+
+    /// Replaces the current ranges with the ranges read from the given path.
+    ///
+    /// # Errors
+    /// If the RangeMessage is in an unexpected format or does not exist, an error will be returned
+    /// and the current ranges are kept.
+    pub fn reload_from_path<P: AsRef<Path>>(&self, path: P) -> Result<(), IsbnRangeError> {
+        self.store(IsbnRange::from_path(path)?);
+        Ok(())
+    }
+
+    /// Replaces the current ranges with the ranges read from the given reader.
+    ///
+    /// # Errors
+    /// If the RangeMessage is in an unexpected format, an error will be returned and the current
+    /// ranges are kept.
+    pub fn reload_from_reader<B: BufRead>(&self, reader: B) -> Result<(), IsbnRangeError> {
+        self.store(IsbnRange::from_reader(reader)?);
+        Ok(())
+    }
+
+    /// The date of the current ranges.
+    pub fn date(&self) -> String {
+        self.load().date().to_string()
+    }
+
+    /// The serial number of the current ranges, if any.
+    pub fn serial_number(&self) -> Option<String> {
+        self.load().serial_number().map(str::to_string)
+    }
+
+    /// Reloads the ranges from the given path whenever its modification time changes, checking
+    /// once every `interval` on a background thread. The thread stops when the returned
+    /// [`RangeWatcher`] is dropped.
+    ///
+    /// Ranges which fail to load, such as a file which is still being written, are retried at
+    /// each check until they load. The last error can be taken with
+    /// [`RangeWatcher::take_error`].
+    ///
+    /// Writers should replace the file atomically, by writing the new ranges to another file
+    /// and renaming it over the watched path. Otherwise a partly written file may be read.
+    pub fn watch<P: AsRef<Path>>(self: &Arc<Self>, path: P, interval: Duration) -> RangeWatcher {
+        let store = Arc::clone(self);
+        let path = path.as_ref().to_path_buf();
+        let error = Arc::new(Mutex::new(None));
+        let (stop, stopped) = mpsc::channel();
+
+        let thread_error = Arc::clone(&error);
+        let thread_path = path.clone();
+        let mut modified = modified_time(&path);
+        let thread = thread::spawn(move || {
+            let path = thread_path;
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                let now = modified_time(&path);
+                if now.is_none() || now == modified {
+                    continue;
+                }
+                // The modification time is only recorded once the file loads, so a file which
+                // was read while being written is read again even if its time does not change.
+                match store.reload_from_path(&path) {
+                    Ok(()) => modified = now,
+                    Err(e) => {
+                        *thread_error.lock().unwrap_or_else(PoisonError::into_inner) = Some(e)
+                    }
+                }
+            }
+        });
+
+        RangeWatcher {
+            path,
+            error,
+            stop,
+            thread: Some(thread),
+        }
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Reloads an [`IsbnRangeStore`] when a file is modified, returned by
+/// [`IsbnRangeStore::watch`]. Stops watching when dropped.
+pub struct RangeWatcher {
+    path: PathBuf,
+    error: Arc<Mutex<Option<IsbnRangeError>>>,
+    stop: Sender<()>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl RangeWatcher {
+    /// The path being watched.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Takes the error of the last reload which failed, if any.
+    pub fn take_error(&self) -> Option<IsbnRangeError> {
+        self.error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+    }
+}
+
+impl Drop for RangeWatcher {
+    fn drop(&mut self) {
+        let _ = self.stop.send(());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::time::Instant;
+
+    use super::super::test::range_message;
+    use super::*;
+
+    fn write_ranges(path: &Path, serial_number: &str, modified: SystemTime) {
+        let xml = range_message(
+            &[(
+                "978",
+                "International ISBN Agency",
+                &[("0000000-9999999", 1)],
+            )],
+            &[("978-0", "English language", &[("0000000-9999999", 2)])],
+        )
+        .replace(
+            "<MessageSerialNumber>1<",
+            &format!("<MessageSerialNumber>{}<", serial_number),
+        );
+        write_file(path, &xml, modified);
+    }
+
+    fn write_file(path: &Path, contents: &str, modified: SystemTime) {
+        fs::write(path, contents).unwrap();
+        File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    }
+
+    /// Waits for the serial number of the store to change from `serial_number`.
+    fn wait_for_change(store: &IsbnRangeStore, serial_number: &str) -> Option<String> {
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_secs(10) {
+            if store.serial_number().as_deref() != Some(serial_number) {
+                return store.serial_number();
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        None
+    }
+
+    #[test]
+    fn test_store() {
+        let store = IsbnRangeStore::from_path("./isbn-ranges/RangeMessage.xml").unwrap();
+        let before = store.load();
+        let xml = range_message(&[], &[]);
+        store.reload_from_reader(xml.as_bytes()).unwrap();
+
+        assert_eq!(store.serial_number().as_deref(), Some("1"));
+        assert_eq!(store.load().ean_ucc_groups().len(), 0);
+        // Snapshots are not affected by reloads.
+        assert_eq!(before.ean_ucc_groups().len(), 2);
+
+        assert!(
+            store
+                .reload_from_reader("<ISBNRangeMessage>".as_bytes())
+                .is_err()
+        );
+        assert_eq!(store.serial_number().as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn test_watch() {
+        let path = std::env::temp_dir().join(format!("isbn-store-{}.xml", std::process::id()));
+        let modified = SystemTime::now() - Duration::from_secs(60);
+        write_ranges(&path, "1", modified);
+
+        let store = Arc::new(IsbnRangeStore::from_path(&path).unwrap());
+        let watcher = store.watch(&path, Duration::from_millis(5));
+        assert_eq!(watcher.path(), path);
+
+        write_ranges(&path, "2", modified + Duration::from_secs(1));
+        assert_eq!(wait_for_change(&store, "1").as_deref(), Some("2"));
+
+        // A partly written file, whose modification time does not change once it is complete.
+        let written = modified + Duration::from_secs(2);
+        write_file(&path, "<ISBNRangeMessage>", written);
+        let start = Instant::now();
+        let error = loop {
+            if let Some(error) = watcher.take_error() {
+                break Some(error);
+            }
+            if start.elapsed() > Duration::from_secs(10) {
+                break None;
+            }
+            thread::sleep(Duration::from_millis(5));
+        };
+        assert!(error.is_some());
+        assert_eq!(store.serial_number().as_deref(), Some("2"));
+
+        write_ranges(&path, "3", written);
+        assert_eq!(wait_for_change(&store, "2").as_deref(), Some("3"));
+
+        drop(watcher);
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,260 @@
+use std::fmt;
+use std::hash::{Hash, RandomState};
+use std::io::BufRead;
+use std::path::Path;
+
+use indexmap::IndexMap;
+
+use super::{IsbnRange, IsbnRangeError, Segment};
+use crate::Rule;
+
+/// An inconsistency in the ISBN ranges, found by [`IsbnRange::validate`].
+#[derive(Debug, PartialEq, Clone, Eq, Hash)]
+#[non_exhaustive]
+pub enum RangeIssue {
+    /// A rule of the group starts after it stops, or stops after `9999999`.
+    InvalidRule { prefix: String, rule: Rule },
+    /// Two rules of the group overlap.
+    Overlap {
+        prefix: String,
+        first: Rule,
+        second: Rule,
+    },
+    /// No rule of the group covers the segments from `start` to `stop`, inclusive.
+    Gap {
+        prefix: String,
+        start: u32,
+        stop: u32,
+    },
+    /// The EAN.UCC prefix of the registration group is not one of the EAN.UCC prefixes.
+    UndeclaredPrefix { prefix: String },
+}
+
+impl RangeIssue {
+    /// The prefix of the group with the issue, such as `978-89`.
+    pub fn prefix(&self) -> &str {
+        match self {
+            RangeIssue::InvalidRule { prefix, .. }
+            | RangeIssue::Overlap { prefix, .. }
+            | RangeIssue::Gap { prefix, .. }
+            | RangeIssue::UndeclaredPrefix { prefix } => prefix,
+        }
+    }
+}
+
+impl fmt::Display for RangeIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RangeIssue::InvalidRule { prefix, rule } => write!(
+                f,
+                "{}: the rule {:07}-{:07} is not a valid range.",
+                prefix, rule.start, rule.stop
+            ),
+            RangeIssue::Overlap {
+                prefix,
+                first,
+                second,
+            } => write!(
+                f,
+                "{}: the rules {:07}-{:07} and {:07}-{:07} overlap.",
+                prefix, first.start, first.stop, second.start, second.stop
+            ),
+            RangeIssue::Gap {
+                prefix,
+                start,
+                stop,
+            } => write!(f, "{}: no rule covers {:07}-{:07}.", prefix, start, stop),
+            RangeIssue::UndeclaredPrefix { prefix } => write!(
+                f,
+                "{}: the EAN.UCC prefix of the registration group is not declared.",
+                prefix
+            ),
+        }
+    }
+}
+
+/// Checks that the rules of each group are valid, do not overlap, and cover every segment.
+fn check_rules<K: Hash + Eq>(
+    issues: &mut Vec<RangeIssue>,
+    segments: &IndexMap<K, Segment, RandomState>,
+) {
+    for segment in segments.values() {
+        let prefix = || segment.prefix.clone();
+        let mut rules: Vec<Rule> = Vec::with_capacity(segment.rules.len());
+        for &rule in &segment.rules {
+            if rule.start > rule.stop || rule.stop > 9_999_999 {
+                issues.push(RangeIssue::InvalidRule {
+                    prefix: prefix(),
+                    rule,
+                });
+            } else {
+                rules.push(rule);
+            }
+        }
+        rules.sort_by_key(|rule| (rule.start, rule.stop));
+
+        // The next segment to be covered, and the rule which covers the segment before it.
+        let mut next = 0;
+        let mut last: Option<Rule> = None;
+        for rule in rules {
+            if rule.start > next {
+                issues.push(RangeIssue::Gap {
+                    prefix: prefix(),
+                    start: next,
+                    stop: rule.start - 1,
+                });
+            } else if let Some(last) = last.filter(|last| rule.start <= last.stop) {
+                issues.push(RangeIssue::Overlap {
+                    prefix: prefix(),
+                    first: last,
+                    second: rule,
+                });
+            }
+            if last.is_none_or(|last| rule.stop > last.stop) {
+                next = rule.stop + 1;
+                last = Some(rule);
+            }
+        }
+        if next <= 9_999_999 {
+            issues.push(RangeIssue::Gap {
+                prefix: prefix(),
+                start: next,
+                stop: 9_999_999,
+            });
+        }
+    }
+}
+
+impl IsbnRange {
+    /// Checks the ISBN ranges for inconsistencies: rules which overlap or are not valid ranges,
+    /// segments of a group which no rule covers, and registration groups whose EAN.UCC prefix
+    /// is not declared.
+    ///
+    /// ```
+    /// use isbn::IsbnRange;
+    ///
+    /// let isbn_ranges = IsbnRange::from_path("isbn-ranges/RangeMessage.xml").unwrap();
+    /// assert_eq!(isbn_ranges.validate(), Ok(()));
+    /// ```
+    /// # Errors
+    /// Returns every inconsistency found, in the order of the RangeMessage.
+    pub fn validate(&self) -> Result<(), Vec<RangeIssue>> {
+        let mut issues = Vec::new();
+        check_rules(&mut issues, &self.ean_ucc_group);
+        check_rules(&mut issues, &self.registration_group);
+        for (&(prefix, _), segment) in &self.registration_group {
+            if !self.ean_ucc_group.contains_key(&prefix) {
+                issues.push(RangeIssue::UndeclaredPrefix {
+                    prefix: segment.prefix.clone(),
+                });
+            }
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(issues)
+        }
+    }
+
+    /// Reads the ISBN ranges from the given path, as with [`IsbnRange::from_path`], and checks
+    /// them with [`IsbnRange::validate`].
+    ///
+    /// # Errors
+    /// If the RangeMessage is in an unexpected format, does not exist, or is inconsistent, an
+    /// error will be returned.
+    pub fn from_path_strict<P: AsRef<Path>>(path: P) -> Result<Self, IsbnRangeError> {
+        let isbn_ranges = Self::from_path(path)?;
+        isbn_ranges
+            .validate()
+            .map_err(IsbnRangeError::Inconsistent)?;
+        Ok(isbn_ranges)
+    }
+
+    /// Reads the ISBN ranges from the given reader, as with [`IsbnRange::from_reader`], and
+    /// checks them with [`IsbnRange::validate`].
+    ///
+    /// # Errors
+    /// If the RangeMessage is in an unexpected format or is inconsistent, an error will be
+    /// returned.
+    pub fn from_reader_strict<B: BufRead>(reader: B) -> Result<Self, IsbnRangeError> {
+        let isbn_ranges = Self::from_reader(reader)?;
+        isbn_ranges
+            .validate()
+            .map_err(IsbnRangeError::Inconsistent)?;
+        Ok(isbn_ranges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test::range_message;
+    use super::*;
+
+    fn rule(start: u32, stop: u32, length: usize) -> Rule {
+        Rule::new(start, stop, length).unwrap()
+    }
+
+    #[test]
+    fn test_validate() {
+        let xml = range_message(
+            &[(
+                "978",
+                "International ISBN Agency",
+                &[("0000000-9999999", 1)],
+            )],
+            &[
+                ("978-0", "English language", &[("0000000-9999999", 2)]),
+                (
+                    "978-1",
+                    "English language",
+                    &[
+                        ("0000000-0999999", 2),
+                        ("0500000-1999999", 3),
+                        ("1500000-1899999", 4),
+                        ("3000000-9899999", 5),
+                    ],
+                ),
+                ("979-10", "France", &[("0000000-9999999", 2)]),
+            ],
+        );
+        let isbn_ranges = IsbnRange::from_reader(xml.as_bytes()).unwrap();
+        let issues = isbn_ranges.validate().unwrap_err();
+
+        assert_eq!(
+            issues,
+            [
+                RangeIssue::Overlap {
+                    prefix: "978-1".to_string(),
+                    first: rule(0, 999_999, 2),
+                    second: rule(500_000, 1_999_999, 3),
+                },
+                RangeIssue::Overlap {
+                    prefix: "978-1".to_string(),
+                    first: rule(500_000, 1_999_999, 3),
+                    second: rule(1_500_000, 1_899_999, 4),
+                },
+                RangeIssue::Gap {
+                    prefix: "978-1".to_string(),
+                    start: 2_000_000,
+                    stop: 2_999_999,
+                },
+                RangeIssue::Gap {
+                    prefix: "978-1".to_string(),
+                    start: 9_900_000,
+                    stop: 9_999_999,
+                },
+                RangeIssue::UndeclaredPrefix {
+                    prefix: "979-10".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            issues[0].to_string(),
+            "978-1: the rules 0000000-0999999 and 0500000-1999999 overlap."
+        );
+
+        let strict = IsbnRange::from_reader_strict(xml.as_bytes());
+        assert!(matches!(strict, Err(IsbnRangeError::Inconsistent(issues)) if issues.len() == 5));
+    }
+}
@@ -0,0 +1,144 @@
+use std::io;
+
+use quick_xml::Writer;
+use quick_xml::escape::partial_escape;
+use quick_xml::events::{BytesDecl, BytesText, Event};
+
+use super::{IsbnRange, Segment};
+
+fn write_tag<W: io::Write>(writer: &mut Writer<W>, name: &str, text: &str) -> io::Result<()> {
+    // Quotes are left as is, as they need no escaping in text.
+    writer
+        .create_element(name)
+        .write_text_content(BytesText::from_escaped(partial_escape(text)))?;
+    Ok(())
+}
+
+fn write_segments<'a, W: io::Write>(
+    writer: &mut Writer<W>,
+    name: &str,
+    segments: impl Iterator<Item = &'a Segment>,
+) -> io::Result<()> {
+    for segment in segments {
+        writer.create_element(name).write_inner_content(|writer| {
+            write_tag(writer, "Prefix", &segment.prefix)?;
+            write_tag(writer, "Agency", &segment.name)?;
+            writer
+                .create_element("Rules")
+                .write_inner_content(|writer| {
+                    for rule in &segment.rules {
+                        writer
+                            .create_element("Rule")
+                            .write_inner_content(|writer| {
+                                write_tag(
+                                    writer,
+                                    "Range",
+                                    &format!("{:07}-{:07}", rule.start, rule.stop),
+                                )?;
+                                write_tag(writer, "Length", &rule.length.to_string())
+                            })?;
+                    }
+                    Ok(())
+                })?;
+            Ok(())
+        })?;
+    }
+    Ok(())
+}
+
+impl IsbnRange {
+    /// Writes the ISBN ranges as a RangeMessage XML document, which can be read back with
+    /// [`IsbnRange::from_reader`].
+    ///
+    /// ```
+    /// use isbn::IsbnRange;
+    ///
+    /// let isbn_ranges = IsbnRange::from_path("isbn-ranges/RangeMessage.xml").unwrap();
+    ///
+    /// let mut xml = Vec::new();
+    /// isbn_ranges.write_xml(&mut xml).unwrap();
+    /// let written = IsbnRange::from_reader(xml.as_slice()).unwrap();
+    ///
+    /// assert_eq!(written.date(), isbn_ranges.date());
+    /// assert!(written.diff(&isbn_ranges).is_empty());
+    /// ```
+    /// # Errors
+    /// Returns any error of the writer.
+    pub fn write_xml<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        let mut writer = Writer::new_with_indent(writer, b' ', 2);
+        writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+        writer
+            .create_element("ISBNRangeMessage")
+            .write_inner_content(|writer| {
+                if let Some(source) = &self.source {
+                    write_tag(writer, "MessageSource", source)?;
+                }
+                if let Some(serial_number) = &self.serial_number {
+                    write_tag(writer, "MessageSerialNumber", serial_number)?;
+                }
+                write_tag(writer, "MessageDate", &self.date)?;
+                writer
+                    .create_element("EAN.UCCPrefixes")
+                    .write_inner_content(|writer| {
+                        write_segments(writer, "EAN.UCC", self.ean_ucc_group.values())
+                    })?;
+                writer
+                    .create_element("RegistrationGroups")
+                    .write_inner_content(|writer| {
+                        write_segments(writer, "Group", self.registration_group.values())
+                    })?;
+                Ok(())
+            })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test::range_message;
+    use super::*;
+
+    #[test]
+    fn test_write_xml() {
+        let xml = range_message(
+            &[
+                (
+                    "978",
+                    "International ISBN Agency",
+                    &[("0000000-9999999", 1)],
+                ),
+                (
+                    "979",
+                    "International ISBN Agency",
+                    &[("0000000-0999999", 0), ("1000000-1299999", 2)],
+                ),
+            ],
+            &[
+                (
+                    "978-0",
+                    "English language",
+                    &[("0000000-1999999", 2), ("2000000-9999999", 0)],
+                ),
+                ("979-10", "Côte d'Ivoire", &[("0000000-9999999", 3)]),
+            ],
+        );
+        let isbn_ranges = IsbnRange::from_reader(xml.as_bytes()).unwrap();
+
+        let mut written = Vec::new();
+        isbn_ranges.write_xml(&mut written).unwrap();
+        let written = IsbnRange::from_reader(written.as_slice()).unwrap();
+
+        assert_eq!(written.source(), isbn_ranges.source());
+        assert_eq!(written.serial_number(), isbn_ranges.serial_number());
+        assert_eq!(written.date(), isbn_ranges.date());
+        assert!(written.diff(&isbn_ranges).is_empty());
+        assert!(
+            written
+                .ean_ucc_groups()
+                .chain(written.registration_groups())
+                .eq(isbn_ranges
+                    .ean_ucc_groups()
+                    .chain(isbn_ranges.registration_groups()))
+        );
+    }
+}
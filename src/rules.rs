@@ -0,0 +1,219 @@
+use crate::{Group, GroupLocale, Isbn, IsbnError};
+
+/// A rule of the ISBN ranges, giving the length of the next element for a range of segments.
+///
+/// A segment is the seven digits following the prefix of an EAN.UCC prefix or registration
+/// group, padded with zeros.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub struct Rule {
+    pub(crate) start: u32,
+    pub(crate) stop: u32,
+    pub(crate) length: usize,
+}
+
+impl Rule {
+    /// Creates a rule for the segments from `start` to `stop`, inclusive, giving the next
+    /// element `length` digits. Returns `None` if `start` is after `stop`, if `stop` has more
+    /// than seven digits, or if `length` is larger than seven.
+    ///
+    /// ```
+    /// use isbn::Rule;
+    ///
+    /// assert_eq!(Rule::new(0, 1_999_999, 2).unwrap().length(), 2);
+    /// assert_eq!(Rule::new(0, 10_000_000, 2), None);
+    /// ```
+    pub const fn new(start: u32, stop: u32, length: usize) -> Option<Rule> {
+        if start > stop || stop > 9_999_999 || length > 7 {
+            return None;
+        }
+        Some(Rule {
+            start,
+            stop,
+            length,
+        })
+    }
+
+    /// The first segment of the range.
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+
+    /// The last segment of the range, inclusive.
+    pub fn stop(&self) -> u32 {
+        self.stop
+    }
+
+    /// The length of the next element, either the registration group or the registrant. This
+    /// is zero if the range is not defined for use at this time.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Whether the range is not defined for use at this time.
+    pub fn is_reserved(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Whether the range includes the segment.
+    pub fn contains(&self, segment: u32) -> bool {
+        self.start <= segment && segment <= self.stop
+    }
+}
+
+/// An EAN.UCC prefix or registration group of the ISBN ranges, along with its rules.
+#[derive(Debug, Clone, Copy, Eq, Hash)]
+pub struct RangeGroup<'a> {
+    pub(crate) prefix: &'a str,
+    pub(crate) agency: &'a str,
+    pub(crate) rules: &'a [Rule],
+}
+
+/// Groups borrowed for different lifetimes can be compared, such as the compiled groups with
+/// those of an [`IsbnRange`](crate::IsbnRange).
+impl<'b> PartialEq<RangeGroup<'b>> for RangeGroup<'_> {
+    fn eq(&self, other: &RangeGroup<'b>) -> bool {
+        self.prefix == other.prefix && self.agency == other.agency && self.rules == other.rules
+    }
+}
+
+impl<'a> RangeGroup<'a> {
+    /// Creates a group with the given prefix, agency and rules, such as for a
+    /// [`RangeSource`](crate::RangeSource).
+    pub const fn new(prefix: &'a str, agency: &'a str, rules: &'a [Rule]) -> Self {
+        RangeGroup {
+            prefix,
+            agency,
+            rules,
+        }
+    }
+
+    /// The prefix, such as `978` for an EAN.UCC prefix or `978-89` for a registration group.
+    pub fn prefix(&self) -> &'a str {
+        self.prefix
+    }
+
+    /// The name of the agency, such as `Korea, Republic`.
+    pub fn agency(&self) -> &'a str {
+        self.agency
+    }
+
+    /// The rules giving the length of the next element.
+    pub fn rules(&self) -> &'a [Rule] {
+        self.rules
+    }
+
+    /// The countries and languages of the registration group, if known. Always `None` for
+    /// EAN.UCC prefixes.
+    pub fn locale(&self) -> Option<GroupLocale<'static>> {
+        Isbn::find_group_locale(self.prefix)
+    }
+
+    /// The rule for the segment, if any.
+    pub fn rule(&self, segment: u32) -> Option<&'a Rule> {
+        self.rules.iter().find(|rule| rule.contains(segment))
+    }
+
+    pub(crate) fn group(&self, segment: u32) -> Result<Group<'a>, IsbnError> {
+        let rule = self.rule(segment).ok_or(IsbnError::InvalidGroup)?;
+        if rule.is_reserved() {
+            return Err(IsbnError::UndefinedRange);
+        }
+        Ok(Group {
+            name: self.agency,
+            segment_length: rule.length,
+        })
+    }
+}
+
+/// The groups and rules matched when looking up an ISBN in the ISBN ranges, returned by
+/// [`Isbn::lookup_range`](crate::Isbn::lookup_range).
+///
+/// The lookup stops at the first rule which is reserved, or at the first group or rule which
+/// is missing.
+///
+/// # Examples
+///
+/// ```
+/// use isbn::{Isbn13, IsbnError};
+///
+/// let isbn_13: Isbn13 = "979-0-000-00000-1".parse().unwrap();
+/// let lookup = isbn_13.lookup_range().unwrap();
+///
+/// assert_eq!(lookup.ean_ucc_group().prefix(), "979");
+/// assert_eq!(lookup.ean_ucc_rule().start(), 0);
+/// assert_eq!(lookup.ean_ucc_rule().stop(), 999_999);
+/// assert!(lookup.is_reserved());
+/// assert_eq!(lookup.registration_group(), None);
+/// assert_eq!(lookup.error(), Some(IsbnError::UndefinedRange));
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub struct RangeLookup<'a> {
+    pub(crate) ean_ucc_group: RangeGroup<'a>,
+    pub(crate) ean_ucc_rule: Rule,
+    pub(crate) registration_group: Option<RangeGroup<'a>>,
+    pub(crate) registrant_rule: Option<Rule>,
+}
+
+impl<'a> RangeLookup<'a> {
+    /// The EAN.UCC prefix of the ISBN.
+    pub fn ean_ucc_group(&self) -> RangeGroup<'a> {
+        self.ean_ucc_group
+    }
+
+    /// The rule of the EAN.UCC prefix giving the length of the registration group.
+    pub fn ean_ucc_rule(&self) -> Rule {
+        self.ean_ucc_rule
+    }
+
+    /// The registration group of the ISBN, if the registration group is defined.
+    pub fn registration_group(&self) -> Option<RangeGroup<'a>> {
+        self.registration_group
+    }
+
+    /// The rule of the registration group giving the length of the registrant, if any.
+    pub fn registrant_rule(&self) -> Option<Rule> {
+        self.registrant_rule
+    }
+
+    /// Whether the ISBN is in a range which is not defined for use at this time.
+    pub fn is_reserved(&self) -> bool {
+        self.ean_ucc_rule.is_reserved() || self.registrant_rule.is_some_and(|r| r.is_reserved())
+    }
+
+    /// The error returned when hyphenating the ISBN, if any.
+    pub fn error(&self) -> Option<IsbnError> {
+        if self.ean_ucc_rule.is_reserved() {
+            return Some(IsbnError::UndefinedRange);
+        }
+        let group_length = match check_group_length(self.ean_ucc_rule.length) {
+            Ok(length) => length,
+            Err(error) => return Some(error),
+        };
+        match self.registrant_rule {
+            None => Some(IsbnError::InvalidGroup),
+            Some(rule) if rule.is_reserved() => Some(IsbnError::UndefinedRange),
+            Some(rule) => check_registrant_length(group_length, rule.length).err(),
+        }
+    }
+}
+
+/// Checks the length of a registration group element, which has at most five digits.
+pub(crate) fn check_group_length(length: usize) -> Result<usize, IsbnError> {
+    match length {
+        1..=5 => Ok(length),
+        _ => Err(IsbnError::InvalidGroup),
+    }
+}
+
+/// Checks the length of a registrant element following a registration group element of
+/// `group_length` digits, which must leave at least one digit for the publication element.
+pub(crate) fn check_registrant_length(
+    group_length: usize,
+    length: usize,
+) -> Result<usize, IsbnError> {
+    if group_length + length < 9 {
+        Ok(length)
+    } else {
+        Err(IsbnError::InvalidGroup)
+    }
+}
@@ -0,0 +1,155 @@
+use core::ops::Range;
+
+use crate::{Isbn, Parser};
+
+/// An iterator over the ISBNs found in a piece of text, created by [`Isbn::find_iter`].
+///
+/// Each item is the byte range of the ISBN within the text, along with the ISBN itself.
+#[derive(Debug, Clone)]
+pub struct FindIter<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+/// Whether `c` may appear between the digits of an ISBN.
+fn is_separator(c: char) -> bool {
+    matches!(
+        c,
+        '-' | '\u{2010}'..='\u{2015}' | '\u{2212}' | '\u{FE63}' | '\u{FF0D}'
+    ) || c.is_whitespace()
+}
+
+/// Whether an ISBN ending right before `rest` is not part of a longer number or word, such as a
+/// trailing `X` after thirteen digits.
+fn is_boundary(rest: &str) -> bool {
+    let mut chars = rest.chars();
+    match chars.next() {
+        Some(c) if c.is_alphanumeric() => false,
+        Some(c) if is_separator(c) && !c.is_whitespace() => {
+            !chars.next().is_some_and(|c| c.is_ascii_digit())
+        }
+        _ => true,
+    }
+}
+
+impl<'a> FindIter<'a> {
+    pub(crate) fn new(text: &'a str) -> Self {
+        FindIter { text, position: 0 }
+    }
+
+    /// Reads the run of digits and separators starting at `start`. Returns the ISBN found at the
+    /// start of the run, if any, and the position to continue searching from.
+    fn read_run(&self, start: usize) -> (Option<(Range<usize>, Isbn)>, usize) {
+        let mut parser = Parser::default();
+        let mut overflow = false;
+        let mut after_separator = false;
+        let mut first_space = None;
+        let mut end = start;
+        let mut ends = [None; 2];
+
+        for (i, c) in self.text[start..].char_indices() {
+            let i = start + i;
+            if c.is_ascii_digit() || (matches!(c, 'X' | 'x') && !overflow) {
+                if !overflow && parser.push(c).is_err() {
+                    if !c.is_ascii_digit() {
+                        break;
+                    }
+                    overflow = true;
+                }
+                end = i + c.len_utf8();
+                after_separator = false;
+                match (overflow, parser.digits.len()) {
+                    (false, 10) => ends[0] = Some(end),
+                    (false, 13) => ends[1] = Some(end),
+                    _ => {}
+                }
+            } else if is_separator(c) && !after_separator {
+                if c.is_whitespace() && first_space.is_none() {
+                    first_space = Some(i);
+                }
+                after_separator = true;
+            } else {
+                break;
+            }
+        }
+
+        let next = first_space.filter(|&i| i < end).unwrap_or(end);
+        for (length, isbn_end) in [(13, ends[1]), (10, ends[0])] {
+            let Some(isbn_end) = isbn_end else { continue };
+            if !is_boundary(&self.text[isbn_end..]) {
+                continue;
+            }
+            let mut candidate = parser.clone();
+            candidate.digits.truncate(length);
+            if let Ok(isbn) = candidate.read_isbn() {
+                return (Some((start..isbn_end, isbn)), isbn_end);
+            }
+        }
+        (None, next)
+    }
+}
+
+impl Iterator for FindIter<'_> {
+    type Item = (Range<usize>, Isbn);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(offset) = self.text[self.position..].find(|c: char| c.is_ascii_digit()) {
+            let start = self.position + offset;
+            let (found, next) = self.read_run(start);
+            self.position = if next > start { next } else { start + 1 };
+            if found.is_some() {
+                return found;
+            }
+        }
+        self.position = self.text.len();
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrayvec::ArrayVec;
+
+    fn find_all(text: &str) -> ArrayVec<(&str, Isbn), 8> {
+        Isbn::find_iter(text)
+            .map(|(range, isbn)| (&text[range], isbn))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_labelled() {
+        let found = find_all("ISBN-13: 978-0-306-40615-7\nISBN-10 0-306-40615-2\nISBN:0306406152");
+        let matched: ArrayVec<&str, 8> = found.iter().map(|(s, _)| *s).collect();
+        assert_eq!(
+            matched.as_slice(),
+            ["978-0-306-40615-7", "0-306-40615-2", "0306406152"]
+        );
+    }
+
+    #[test]
+    fn test_find_unicode_dashes_and_spaces() {
+        let found =
+            find_all("see 978\u{2013}3\u{2013}16\u{2013}148410\u{2013}0 or 978 3 16 148410 0.");
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].1, found[1].1);
+    }
+
+    #[test]
+    fn test_find_skips_invalid_and_longer_numbers() {
+        assert!(find_all("9780306406158 and 97803064061571 and 19780306406157").is_empty());
+        assert!(find_all("0-306-40615-2-1").is_empty());
+        assert!(find_all("97803064061577x 9780306406157X 9780306406157a").is_empty());
+        assert!(find_all("0-306-40615-2b").is_empty());
+        assert_eq!(find_all("tel 1234 9780306406157").len(), 1);
+        assert_eq!(find_all("0-9752298-0-X.")[0].0, "0-9752298-0-X");
+    }
+
+    #[test]
+    fn test_find_adjacent() {
+        let found = find_all("9780306406157 0306406152");
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].0, "9780306406157");
+        assert_eq!(found[1].0, "0306406152");
+    }
+}
@@ -0,0 +1,367 @@
+//! Serde support, enabled by the `serialize` feature.
+//!
+//! By default, ISBNs are serialized as strings of digits without hyphens, and deserialized from
+//! strings with or without hyphens. The modules in this module can be used with
+//! `#[serde(with = "...")]` to choose another format:
+//!
+//! * [`compact`]: digits without hyphens, as by default.
+//! * [`hyphenated`]: hyphenated according to the ISBN ranges, falling back to [`compact`] if the
+//!   ISBN is in an undefined range.
+//! * [`structural`]: the digit arrays used before ISBNs were serialized as strings.
+//!
+//! ```
+//! use isbn::Isbn13;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Book {
+//!     isbn: Isbn13,
+//!     #[serde(with = "isbn::serialize::hyphenated")]
+//!     display_isbn: Isbn13,
+//! }
+//!
+//! let isbn: Isbn13 = "9781492067665".parse().unwrap();
+//! let book = Book { isbn, display_isbn: isbn };
+//! assert_eq!(
+//!     serde_json::to_string(&book).unwrap(),
+//!     r#"{"isbn":"9781492067665","display_isbn":"978-1-4920-6766-5"}"#
+//! );
+//! ```
+use core::fmt::{self, Write};
+use core::marker::PhantomData;
+use core::str::FromStr;
+
+use arrayvec::ArrayString;
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use crate::{CompactIsbn, Isbn, Isbn10, Isbn13, IsbnError};
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for crate::Isbn {}
+    impl Sealed for crate::Isbn10 {}
+    impl Sealed for crate::Isbn13 {}
+    impl Sealed for crate::CompactIsbn {}
+}
+
+/// An ISBN type which can be used with the modules in [`serialize`](self).
+///
+/// This trait is sealed and can not be implemented outside of this crate.
+pub trait SerializeIsbn: sealed::Sealed + fmt::Display + FromStr<Err = IsbnError> + Sized {
+    #[doc(hidden)]
+    fn hyphenate(&self) -> Result<ArrayString<17>, IsbnError>;
+
+    #[doc(hidden)]
+    fn serialize_structural<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+
+    #[doc(hidden)]
+    fn deserialize_structural<'de, D: Deserializer<'de>>(deserializer: D)
+    -> Result<Self, D::Error>;
+}
+
+/// Serializes ISBNs as strings of digits without hyphens.
+pub mod compact {
+    use super::*;
+
+    /// Serializes an ISBN as a string of digits.
+    ///
+    /// # Errors
+    /// Returns any error of the serializer.
+    pub fn serialize<T: SerializeIsbn, S: Serializer>(
+        isbn: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serialize_digits(isbn, serializer)
+    }
+
+    /// Deserializes an ISBN from a string, with or without hyphens.
+    ///
+    /// # Errors
+    /// If the string is not a valid ISBN, an error will be returned.
+    pub fn deserialize<'de, T: SerializeIsbn, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        deserializer.deserialize_str(IsbnVisitor(PhantomData))
+    }
+}
+
+/// Serializes ISBNs as hyphenated strings, or as digits if the ISBN can not be hyphenated.
+pub mod hyphenated {
+    use super::*;
+
+    /// Serializes an ISBN as a hyphenated string.
+    ///
+    /// # Errors
+    /// Returns any error of the serializer.
+    pub fn serialize<T: SerializeIsbn, S: Serializer>(
+        isbn: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match isbn.hyphenate() {
+            Ok(hyphenated) => serializer.serialize_str(&hyphenated),
+            Err(_) => compact::serialize(isbn, serializer),
+        }
+    }
+
+    /// Deserializes an ISBN from a string, with or without hyphens.
+    ///
+    /// # Errors
+    /// If the string is not a valid ISBN, an error will be returned.
+    pub fn deserialize<'de, T: SerializeIsbn, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        compact::deserialize(deserializer)
+    }
+}
+
+/// Serializes ISBNs as structures with an array of digits, such as `{"digits":[8,9,...]}`, and
+/// [`Isbn`] as an enum of these structures.
+///
+/// This is the format used before ISBNs were serialized as strings. Unlike before, the digits are
+/// validated when deserializing.
+pub mod structural {
+    use super::*;
+
+    /// Serializes an ISBN as a structure with an array of digits.
+    ///
+    /// # Errors
+    /// Returns any error of the serializer.
+    pub fn serialize<T: SerializeIsbn, S: Serializer>(
+        isbn: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        isbn.serialize_structural(serializer)
+    }
+
+    /// Deserializes an ISBN from a structure with an array of digits.
+    ///
+    /// # Errors
+    /// If the digits are not a valid ISBN, an error will be returned.
+    pub fn deserialize<'de, T: SerializeIsbn, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        T::deserialize_structural(deserializer)
+    }
+}
+
+fn serialize_digits<T: fmt::Display, S: Serializer>(
+    isbn: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut s = ArrayString::<13>::new();
+    write!(s, "{}", isbn).map_err(|_| serde::ser::Error::custom("ISBN is too long"))?;
+    serializer.serialize_str(&s)
+}
+
+struct IsbnVisitor<T>(PhantomData<T>);
+
+impl<T: FromStr<Err = IsbnError>> Visitor<'_> for IsbnVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an ISBN string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        v.parse().map_err(E::custom)
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "Isbn10")]
+struct Isbn10Digits {
+    digits: [u8; 10],
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "Isbn13")]
+struct Isbn13Digits {
+    digits: [u8; 13],
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "Isbn")]
+enum IsbnDigits {
+    _10(Isbn10Digits),
+    _13(Isbn13Digits),
+}
+
+impl SerializeIsbn for Isbn10 {
+    fn hyphenate(&self) -> Result<ArrayString<17>, IsbnError> {
+        Isbn10::hyphenate(self)
+    }
+
+    fn serialize_structural<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Isbn10Digits {
+            digits: self.digits,
+        }
+        .serialize(serializer)
+    }
+
+    fn deserialize_structural<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let Isbn10Digits { digits } = Isbn10Digits::deserialize(deserializer)?;
+        Isbn10::new(digits).map_err(de::Error::custom)
+    }
+}
+
+impl SerializeIsbn for Isbn13 {
+    fn hyphenate(&self) -> Result<ArrayString<17>, IsbnError> {
+        Isbn13::hyphenate(self)
+    }
+
+    fn serialize_structural<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Isbn13Digits {
+            digits: self.digits,
+        }
+        .serialize(serializer)
+    }
+
+    fn deserialize_structural<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let Isbn13Digits { digits } = Isbn13Digits::deserialize(deserializer)?;
+        Isbn13::new(digits).map_err(de::Error::custom)
+    }
+}
+
+impl SerializeIsbn for Isbn {
+    fn hyphenate(&self) -> Result<ArrayString<17>, IsbnError> {
+        Isbn::hyphenate(self)
+    }
+
+    fn serialize_structural<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Isbn::_10(isbn) => IsbnDigits::_10(Isbn10Digits {
+                digits: isbn.digits,
+            }),
+            Isbn::_13(isbn) => IsbnDigits::_13(Isbn13Digits {
+                digits: isbn.digits,
+            }),
+        }
+        .serialize(serializer)
+    }
+
+    fn deserialize_structural<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        match IsbnDigits::deserialize(deserializer)? {
+            IsbnDigits::_10(Isbn10Digits { digits }) => Isbn10::new(digits).map(Isbn::_10),
+            IsbnDigits::_13(Isbn13Digits { digits }) => Isbn13::new(digits).map(Isbn::_13),
+        }
+        .map_err(de::Error::custom)
+    }
+}
+
+/// Uses the format of the equivalent [`Isbn13`].
+impl SerializeIsbn for CompactIsbn {
+    fn hyphenate(&self) -> Result<ArrayString<17>, IsbnError> {
+        CompactIsbn::hyphenate(*self)
+    }
+
+    fn serialize_structural<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_isbn13().serialize_structural(serializer)
+    }
+
+    fn deserialize_structural<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        Isbn13::deserialize_structural(deserializer).map(CompactIsbn::new)
+    }
+}
+
+macro_rules! impl_serde_str {
+    ($($t:ty),*) => {$(
+        impl Serialize for $t {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize_digits(self, serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $t {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.deserialize_str(IsbnVisitor(PhantomData))
+            }
+        }
+    )*};
+}
+
+impl_serde_str!(Isbn, Isbn10, Isbn13, CompactIsbn);
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use crate::{CompactIsbn, Isbn, Isbn10, Isbn13};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Book {
+        isbn: Isbn,
+        #[serde(with = "super::hyphenated")]
+        hyphenated: Isbn13,
+        #[serde(with = "super::structural")]
+        structural: Isbn,
+    }
+
+    #[test]
+    fn test_serialize_strings() {
+        let isbn_10: Isbn10 = "89-6626-126-4".parse().unwrap();
+        let book = Book {
+            isbn: Isbn::_10(isbn_10),
+            hyphenated: "9791090636071".parse().unwrap(),
+            structural: Isbn::_10(isbn_10),
+        };
+        let json = serde_json::to_string(&book).unwrap();
+        assert_eq!(
+            json,
+            r#"{"isbn":"8966261264","hyphenated":"979-10-90636-07-1","structural":{"_10":{"digits":[8,9,6,6,2,6,1,2,6,4]}}}"#
+        );
+        assert_eq!(serde_json::from_str::<Book>(&json).unwrap(), book);
+
+        let compact = CompactIsbn::from(isbn_10);
+        assert_eq!(
+            serde_json::to_string(&compact).unwrap(),
+            r#""9788966261260""#
+        );
+        assert_eq!(
+            serde_json::from_str::<CompactIsbn>(r#""978-89-6626-126-0""#).unwrap(),
+            compact
+        );
+        assert_eq!(
+            super::hyphenated::serialize(&compact, serde_json::value::Serializer).unwrap(),
+            "978-89-6626-126-0"
+        );
+        let structural =
+            super::structural::serialize(&compact, serde_json::value::Serializer).unwrap();
+        assert_eq!(
+            serde_json::to_string(&structural).unwrap(),
+            r#"{"digits":[9,7,8,8,9,6,6,2,6,1,2,6,0]}"#
+        );
+        assert_eq!(
+            super::structural::deserialize::<CompactIsbn, _>(structural).unwrap(),
+            compact
+        );
+
+        // Undefined ranges can not be hyphenated and fall back to digits.
+        let undefined: Isbn13 = "9790000000001".parse().unwrap();
+        assert_eq!(
+            super::hyphenated::serialize(&undefined, serde_json::value::Serializer).unwrap(),
+            "9790000000001"
+        );
+    }
+
+    #[test]
+    fn test_deserialize_validates() {
+        assert!(serde_json::from_str::<Isbn13>(r#""978-1-4920-6766-6""#).is_err());
+        assert!(serde_json::from_str::<Isbn>(r#""89-6626-126""#).is_err());
+        assert!(
+            super::structural::deserialize::<Isbn13, _>(&mut serde_json::Deserializer::from_str(
+                r#"{"digits":[9,7,8,1,4,9,2,0,6,7,6,6,6]}"#
+            ))
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,281 @@
+use arrayvec::ArrayString;
+
+use crate::{
+    Audit, EAN_UCC_GROUPS, Isbn, IsbnBlock, IsbnError, IsbnObject, IsbnParts, IsbnRef,
+    REGISTRATION_GROUPS, RangeGroup, RangeLookup, check_hyphens,
+};
+
+/// A source of ISBN ranges, which finds the EAN.UCC prefixes and registration groups used to
+/// hyphenate and validate ISBNs.
+///
+/// This is implemented by [`CompiledRanges`], the ranges compiled into this crate, and by
+/// [`IsbnRange`](crate::IsbnRange). Other sources only need to implement the two lookups.
+///
+/// Sources can be used as `dyn RangeSource`, such as to choose between ranges at runtime.
+/// [`RangeSource::audit`] is only available on sized sources, so a `&dyn RangeSource` is
+/// audited through its reference, which is itself a source.
+///
+/// # Examples
+///
+/// ```
+/// use isbn::{Isbn13, IsbnError, RangeGroup, RangeSource, Rule};
+///
+/// /// Only knows the registration group of Korea.
+/// struct Korea;
+///
+/// static EAN_UCC_RULES: [Rule; 1] = [Rule::new(0, 9_999_999, 2).unwrap()];
+/// static KOREA_RULES: [Rule; 1] = [Rule::new(0, 9_999_999, 4).unwrap()];
+///
+/// impl RangeSource for Korea {
+///     fn find_ean_ucc_group(&self, prefix: [u8; 3]) -> Option<RangeGroup<'_>> {
+///         (prefix == [9, 7, 8])
+///             .then(|| RangeGroup::new("978", "International ISBN Agency", &EAN_UCC_RULES))
+///     }
+///
+///     fn find_registration_group(
+///         &self,
+///         prefix: [u8; 3],
+///         registration_group: &[u8],
+///     ) -> Option<RangeGroup<'_>> {
+///         (prefix == [9, 7, 8] && registration_group == [8, 9])
+///             .then(|| RangeGroup::new("978-89", "Korea, Republic", &KOREA_RULES))
+///     }
+/// }
+///
+/// let isbn_13: Isbn13 = "9788966261260".parse().unwrap();
+/// assert_eq!(Korea.hyphenate((&isbn_13).into()).unwrap().as_str(), "978-89-6626-126-0");
+///
+/// let isbn_13: Isbn13 = "9781492067665".parse().unwrap();
+/// assert_eq!(Korea.hyphenate((&isbn_13).into()), Err(IsbnError::InvalidGroup));
+/// ```
+///
+/// ```
+/// use isbn::{AuditStatus, CompiledRanges, Isbn, RangeSource};
+///
+/// let source: &dyn RangeSource = &CompiledRanges;
+/// let isbn: Isbn = "9781492067665".parse().unwrap();
+///
+/// assert_eq!(source.hyphenate((&isbn).into()).unwrap().as_str(), "978-1-4920-6766-5");
+/// assert_eq!(source.parse_hyphenated("978-1-4920-6766-5"), Ok(isbn));
+///
+/// let (_, status) = (&source).audit(["978-1-4920-6766-5"]).next().unwrap();
+/// assert_eq!(status, AuditStatus::Unchanged);
+/// ```
+pub trait RangeSource {
+    /// Finds the EAN.UCC prefix with the given digits, such as `[9, 7, 8]`.
+    fn find_ean_ucc_group(&self, prefix: [u8; 3]) -> Option<RangeGroup<'_>>;
+
+    /// Finds the registration group with the given digits of the EAN.UCC prefix and of the
+    /// registration group element, such as `[9, 7, 8]` and `[8, 9]`.
+    fn find_registration_group(
+        &self,
+        prefix: [u8; 3],
+        registration_group: &[u8],
+    ) -> Option<RangeGroup<'_>>;
+
+    /// Hyphenate an ISBN into its parts, as with [`Isbn::hyphenate`].
+    ///
+    /// # Errors
+    /// If the ISBN is not valid, as determined by `self`, an error will be returned.
+    fn hyphenate(&self, isbn: IsbnRef<'_>) -> Result<ArrayString<17>, IsbnError> {
+        match isbn {
+            IsbnRef::_10(isbn) => isbn.trait_hyphenate(self),
+            IsbnRef::_13(isbn) => isbn.trait_hyphenate(self),
+        }
+    }
+
+    /// Look up the groups and rules of `self` matching the ISBN, as with
+    /// [`Isbn::lookup_range`].
+    ///
+    /// # Errors
+    /// If the EAN.UCC prefix of the ISBN or its rule is not found, or the rule gives the
+    /// registration group more than five digits, an error will be returned.
+    fn lookup_range(&self, isbn: IsbnRef<'_>) -> Result<RangeLookup<'_>, IsbnError> {
+        match isbn {
+            IsbnRef::_10(isbn) => isbn.trait_lookup_range(self),
+            IsbnRef::_13(isbn) => isbn.trait_lookup_range(self),
+        }
+    }
+
+    /// Retrieve the name of the registration group, as with [`Isbn::registration_group`].
+    ///
+    /// # Errors
+    /// If the ISBN is not valid, as determined by `self`, an error will be returned.
+    fn get_registration_group(&self, isbn: IsbnRef<'_>) -> Result<&str, IsbnError> {
+        match isbn {
+            IsbnRef::_10(isbn) => isbn.trait_registration_group(self),
+            IsbnRef::_13(isbn) => isbn.trait_registration_group(self),
+        }
+    }
+
+    /// Split an ISBN into its parts, as with [`Isbn::parts`].
+    ///
+    /// # Errors
+    /// If the ISBN is not valid, as determined by `self`, an error will be returned.
+    fn parts(&self, isbn: IsbnRef<'_>) -> Result<IsbnParts<'_>, IsbnError> {
+        match isbn {
+            IsbnRef::_10(isbn) => isbn.trait_parts(self),
+            IsbnRef::_13(isbn) => isbn.trait_parts(self),
+        }
+    }
+
+    /// Parse an ISBN10 or ISBN13, requiring any hyphens to separate the elements given by
+    /// `self`, as with [`Isbn::parse_hyphenated`].
+    ///
+    /// # Errors
+    /// If the string is not an ISBN, its range is not defined by `self`, or its hyphens are
+    /// misplaced, an error will be returned.
+    fn parse_hyphenated(&self, s: &str) -> Result<Isbn, IsbnError> {
+        let isbn: Isbn = s.parse()?;
+        check_hyphens(s, self.hyphenate((&isbn).into())?)?;
+        Ok(isbn)
+    }
+
+    /// Look up the block of ISBNs available to a registrant, given its hyphenated prefix
+    /// such as `978-1-4920`.
+    ///
+    /// # Errors
+    /// If the prefix is malformed, or its registration group or registrant do not line up with
+    /// the ranges in `self`, an error will be returned.
+    fn block(&self, prefix: &str) -> Result<IsbnBlock, IsbnError> {
+        IsbnBlock::parse_with(prefix, |isbn| Ok(isbn.trait_locate(self)?.0))
+    }
+
+    /// Compare stored, hyphenated ISBNs with `self`, as with [`Isbn::audit`].
+    fn audit<I>(&self, hyphenated: I) -> Audit<'_, I::IntoIter, Self>
+    where
+        Self: Sized,
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        Audit::new(hyphenated.into_iter(), self)
+    }
+}
+
+impl<S: RangeSource + ?Sized> RangeSource for &S {
+    fn find_ean_ucc_group(&self, prefix: [u8; 3]) -> Option<RangeGroup<'_>> {
+        (**self).find_ean_ucc_group(prefix)
+    }
+
+    fn find_registration_group(
+        &self,
+        prefix: [u8; 3],
+        registration_group: &[u8],
+    ) -> Option<RangeGroup<'_>> {
+        (**self).find_registration_group(prefix, registration_group)
+    }
+}
+
+/// The ISBN ranges compiled into this crate, which are used by methods such as
+/// [`Isbn::hyphenate`].
+///
+/// ```
+/// use isbn::{CompiledRanges, Isbn, RangeSource};
+///
+/// let isbn: Isbn = "9781492067665".parse().unwrap();
+/// assert_eq!(CompiledRanges.hyphenate((&isbn).into()), isbn.hyphenate());
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash, Default)]
+pub struct CompiledRanges;
+
+impl RangeSource for CompiledRanges {
+    fn find_ean_ucc_group(&self, prefix: [u8; 3]) -> Option<RangeGroup<'_>> {
+        let index = Isbn::find_ean_ucc_group(pack_digits(&prefix)? as u16)?;
+        Some(EAN_UCC_GROUPS[index])
+    }
+
+    fn find_registration_group(
+        &self,
+        prefix: [u8; 3],
+        registration_group: &[u8],
+    ) -> Option<RangeGroup<'_>> {
+        let index = Isbn::find_registration_group(
+            pack_digits(&prefix)? as u16,
+            pack_digits(registration_group)?,
+        )?;
+        Some(REGISTRATION_GROUPS[index])
+    }
+}
+
+/// Stores each digit in four bits, as in the keys of the ISBN ranges. Returns `None` if there
+/// are more than seven digits, or any digit is larger than nine.
+pub(crate) fn pack_digits(digits: &[u8]) -> Option<u32> {
+    if digits.len() > 7 {
+        return None;
+    }
+    digits.iter().try_fold(0, |packed, &digit| {
+        (digit < 10).then_some((packed << 4) | u32::from(digit))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Isbn13, Rule};
+
+    /// Gives every registration group two digits and every registrant seven.
+    struct TooLong;
+
+    static EAN_UCC_RULES: [Rule; 1] = [Rule::new(0, 9_999_999, 2).unwrap()];
+    static GROUP_RULES: [Rule; 1] = [Rule::new(0, 9_999_999, 7).unwrap()];
+
+    impl RangeSource for TooLong {
+        fn find_ean_ucc_group(&self, _: [u8; 3]) -> Option<RangeGroup<'_>> {
+            Some(RangeGroup::new("978", "", &EAN_UCC_RULES))
+        }
+
+        fn find_registration_group(&self, _: [u8; 3], _: &[u8]) -> Option<RangeGroup<'_>> {
+            Some(RangeGroup::new("978-89", "", &GROUP_RULES))
+        }
+    }
+
+    #[test]
+    fn test_source_elements_too_long() {
+        let isbn_13: Isbn13 = "9788966261260".parse().unwrap();
+        let isbn = IsbnRef::from(&isbn_13);
+        assert_eq!(
+            TooLong.hyphenate(isbn.clone()),
+            Err(IsbnError::InvalidGroup)
+        );
+        assert_eq!(TooLong.parts(isbn.clone()), Err(IsbnError::InvalidGroup));
+        assert_eq!(
+            TooLong.lookup_range(isbn).unwrap().error(),
+            Some(IsbnError::InvalidGroup)
+        );
+    }
+
+    /// Gives every registration group seven digits.
+    struct LongGroup;
+
+    static LONG_GROUP_RULES: [Rule; 1] = [Rule::new(0, 9_999_999, 7).unwrap()];
+
+    impl RangeSource for LongGroup {
+        fn find_ean_ucc_group(&self, _: [u8; 3]) -> Option<RangeGroup<'_>> {
+            Some(RangeGroup::new("978", "", &LONG_GROUP_RULES))
+        }
+
+        fn find_registration_group(&self, _: [u8; 3], _: &[u8]) -> Option<RangeGroup<'_>> {
+            Some(RangeGroup::new("978-8966261", "", &GROUP_RULES))
+        }
+    }
+
+    #[test]
+    fn test_source_group_too_long() {
+        let isbn_13: Isbn13 = "9788966261260".parse().unwrap();
+        let isbn = IsbnRef::from(&isbn_13);
+        assert_eq!(
+            LongGroup.hyphenate(isbn.clone()),
+            Err(IsbnError::InvalidGroup)
+        );
+        assert_eq!(LongGroup.lookup_range(isbn), Err(IsbnError::InvalidGroup));
+    }
+
+    #[test]
+    fn test_pack_digits() {
+        assert_eq!(pack_digits(&[9, 7, 8]), Some(0x978));
+        assert_eq!(pack_digits(&[]), Some(0));
+        assert_eq!(pack_digits(&[1, 0]), Some(0x10));
+        assert_eq!(pack_digits(&[1; 8]), None);
+        assert_eq!(pack_digits(&[10]), None);
+    }
+}